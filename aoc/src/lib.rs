@@ -0,0 +1,110 @@
+//! Every day's solutions behind a common signature, shared by the runner and the
+//! real input tests.
+
+use common::ParseResult;
+
+/// Solves one part of a day for the given input, formatting the answer
+pub type Solver = fn(&str) -> ParseResult<String>;
+
+pub const NUM_DAYS: usize = 25;
+
+/// Every day's (part 1, part 2) solutions, indexed by day - 1. Day 25 has no part 2.
+pub const SOLVERS: [(Solver, Option<Solver>); NUM_DAYS] = [
+    (
+        |input| day1::part1(input).map(|a| a.to_string()),
+        Some(|input| day1::part2(input).map(|a| a.to_string())),
+    ),
+    (
+        |input| day2::part1(input).map(|a| a.to_string()),
+        Some(|input| day2::part2(input).map(|a| a.to_string())),
+    ),
+    (
+        |input| day3::part1(input).map(|a| a.to_string()),
+        Some(|input| day3::part2(input).map(|a| a.to_string())),
+    ),
+    (
+        |input| day4::part1(input).map(|a| a.to_string()),
+        Some(|input| day4::part2(input).map(|a| a.to_string())),
+    ),
+    (
+        |input| day5::part1(input).map(|a| a.to_string()),
+        Some(|input| day5::part2(input).map(|a| a.to_string())),
+    ),
+    (
+        |input| day6::part1(input).map(|a| a.to_string()),
+        Some(|input| day6::part2(input).map(|a| a.to_string())),
+    ),
+    (
+        |input| day7::part1(input).map(|a| a.to_string()),
+        Some(|input| day7::part2(input).map(|a| a.to_string())),
+    ),
+    (
+        |input| day8::part1(input).map(|a| a.to_string()),
+        Some(|input| day8::part2(input).map(|a| a.to_string())),
+    ),
+    (
+        |input| day9::part1(input).map(|a| a.to_string()),
+        Some(|input| day9::part2(input).map(|a| a.to_string())),
+    ),
+    (
+        |input| day10::part1(input).map(|a| a.to_string()),
+        Some(|input| day10::part2(input).map(|a| a.to_string())),
+    ),
+    (
+        |input| day11::part1(input).map(|a| a.to_string()),
+        Some(|input| day11::part2(input).map(|a| a.to_string())),
+    ),
+    (
+        |input| day12::part1(input).map(|a| a.to_string()),
+        Some(|input| day12::part2(input).map(|a| a.to_string())),
+    ),
+    (
+        |input| day13::part1(input).map(|a| a.to_string()),
+        Some(|input| day13::part2(input).map(|a| a.to_string())),
+    ),
+    (
+        |input| day14::part1(input).map(|a| a.to_string()),
+        Some(|input| day14::part2(input).map(|a| a.to_string())),
+    ),
+    (
+        |input| day15::part1(input).map(|a| a.to_string()),
+        Some(|input| day15::part2(input).map(|a| a.to_string())),
+    ),
+    (
+        |input| day16::part1(input).map(|a| a.to_string()),
+        Some(|input| day16::part2(input).map(|a| a.to_string())),
+    ),
+    (
+        |input| day17::part1(input).map(|a| a.to_string()),
+        Some(|input| day17::part2(input).map(|a| a.to_string())),
+    ),
+    (
+        |input| day18::part1(input).map(|a| a.to_string()),
+        Some(|input| day18::part2(input).map(|a| a.to_string())),
+    ),
+    (
+        |input| day19::part1(input).map(|a| a.to_string()),
+        Some(|input| day19::part2(input).map(|a| a.to_string())),
+    ),
+    (
+        |input| day20::part1(input).map(|a| a.to_string()),
+        Some(|input| day20::part2(input).map(|a| a.to_string())),
+    ),
+    (
+        |input| day21::part1(input, 64).map(|a| a.to_string()),
+        Some(|input| day21::part2(input, 26501365).map(|a| a.to_string())),
+    ),
+    (
+        |input| day22::part1(input).map(|a| a.to_string()),
+        Some(|input| day22::part2(input).map(|a| a.to_string())),
+    ),
+    (
+        |input| day23::part1(input).map(|a| a.to_string()),
+        Some(|input| day23::part2(input).map(|a| a.to_string())),
+    ),
+    (
+        |input| day24::part1(input, 200000000000000.0, 400000000000000.0).map(|a| a.to_string()),
+        Some(|input| day24::part2(input).map(|a| a.to_string())),
+    ),
+    (|input| day25::part1(input).map(|a| a.to_string()), None),
+];
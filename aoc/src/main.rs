@@ -0,0 +1,140 @@
+use aoc::{NUM_DAYS, SOLVERS};
+use clap::{arg, command, Command};
+use common::cli::{self, Part};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use std::{fs, io, process};
+
+fn main() -> Result<(), io::Error> {
+    let matches = command!()
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("run")
+                .about("Runs the solutions for the given days")
+                .arg(
+                    arg!(<days> "Day to run: a single day (17), a range (1-5) or all")
+                        .value_parser(parse_days),
+                )
+                .arg(
+                    arg!(--inputs <DIR> "Directory holding the dayNN/input.txt files")
+                        .value_parser(clap::value_parser!(PathBuf))
+                        .default_value(cli::INPUTS_DIR),
+                )
+                .arg(cli::part_arg()),
+        )
+        .get_matches();
+
+    if let Some(("run", matches)) = matches.subcommand() {
+        let days = matches.get_one::<Vec<usize>>("days").unwrap();
+        let inputs = matches.get_one::<PathBuf>("inputs").unwrap();
+        let part = *matches.get_one::<Part>("part").unwrap();
+
+        if !run(days, inputs, part) {
+            process::exit(1);
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses a day selection of the form `17`, `1-5` or `all` into a list of days
+fn parse_days(days: &str) -> Result<Vec<usize>, String> {
+    let parse_day = |s: &str| match s.trim().parse::<usize>() {
+        Ok(d) if (1..=NUM_DAYS).contains(&d) => Ok(d),
+        _ => Err(format!("invalid day \"{}\", expected 1-{}", s, NUM_DAYS)),
+    };
+
+    if days == "all" {
+        return Ok((1..=NUM_DAYS).collect());
+    }
+
+    match days.split_once('-') {
+        Some((start, end)) => {
+            let (start, end) = (parse_day(start)?, parse_day(end)?);
+
+            if start > end {
+                return Err(format!("invalid range \"{}\"", days));
+            }
+
+            Ok((start..=end).collect())
+        }
+        None => Ok(vec![parse_day(days)?]),
+    }
+}
+
+fn format_duration(d: Duration) -> String {
+    if d.as_secs() > 0 {
+        format!("{:.2}s", d.as_secs_f64())
+    } else {
+        format!("{:.2}ms", d.as_secs_f64() * 1000.0)
+    }
+}
+
+/// Runs and prints the table of answers, returning false if any input failed to parse
+fn run(days: &[usize], inputs: &Path, part: Part) -> bool {
+    let mut total = Duration::ZERO;
+    let mut ok = true;
+
+    println!(
+        "{:>3} | {:>4} | {:>20} | {:>10}",
+        "day", "part", "answer", "time"
+    );
+    println!("{:-<4}+{:-<6}+{:-<22}+{:-<11}", "", "", "", "");
+
+    for day in days.iter().copied() {
+        let path = cli::input_path(inputs, day as u32, None);
+        let input = match fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(e) => {
+                println!("{:>3} | {:>4} | {}: {}", day, "", path.display(), e);
+                continue;
+            }
+        };
+
+        let (part1, part2) = SOLVERS[day - 1];
+        let mut solvers = Vec::new();
+
+        if part.runs_part1() {
+            solvers.push((1, part1));
+        }
+
+        if let (true, Some(part2)) = (part.runs_part2(), part2) {
+            solvers.push((2, part2));
+        }
+
+        for (n, solver) in solvers {
+            let start = Instant::now();
+            let answer = solver(&input);
+            let elapsed = start.elapsed();
+            total += elapsed;
+
+            let answer = match answer {
+                Ok(answer) => answer,
+                Err(e) => {
+                    ok = false;
+                    println!("{:>3} | {:>4} | {}:{}", day, n, path.display(), e);
+                    continue;
+                }
+            };
+
+            println!(
+                "{:>3} | {:>4} | {:>20} | {:>10}",
+                day,
+                n,
+                answer,
+                format_duration(elapsed)
+            );
+        }
+    }
+
+    println!("{:-<4}+{:-<6}+{:-<22}+{:-<11}", "", "", "", "");
+    println!(
+        "{:>3} | {:>4} | {:>20} | {:>10}",
+        "",
+        "",
+        "total",
+        format_duration(total)
+    );
+
+    ok
+}
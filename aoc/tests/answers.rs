@@ -0,0 +1,71 @@
+//! Checks every day against the answers in `inputs/answers.toml`, which look like
+//!
+//! ```toml
+//! [day01]
+//! part1 = 54388
+//! part2 = 53515
+//! ```
+//!
+//! The puzzle inputs and their answers aren't committed and some days take a while on
+//! the real input, so this is opt-in:
+//!
+//! ```text
+//! cargo test --release -p aoc -- --ignored
+//! ```
+//!
+//! Days without an `input.txt` or without answers are skipped.
+
+use aoc::SOLVERS;
+use common::cli;
+use std::fs;
+use std::path::Path;
+use toml::{Table, Value};
+
+#[test]
+#[ignore]
+fn real_inputs() {
+    let inputs = Path::new(cli::INPUTS_DIR);
+    let answers: Table = match fs::read_to_string(inputs.join("answers.toml")) {
+        Ok(text) => text.parse().expect("inputs/answers.toml is not valid TOML"),
+        Err(_) => {
+            eprintln!("no inputs/answers.toml, skipping");
+            return;
+        }
+    };
+    let mut failures = Vec::new();
+    let mut checked = 0;
+
+    for (day, (part1, part2)) in (1..).zip(SOLVERS) {
+        let Some(expected) = answers.get(&format!("day{:02}", day)) else {
+            continue;
+        };
+        let Ok(input) = fs::read_to_string(cli::input_path(inputs, day, None)) else {
+            continue;
+        };
+
+        for (n, solver) in [(1, Some(part1)), (2, part2)] {
+            let (Some(solver), Some(want)) = (solver, expected.get(format!("part{}", n))) else {
+                continue;
+            };
+            let want = match want {
+                Value::String(s) => s.clone(),
+                v => v.to_string(),
+            };
+            let got = match solver(&input) {
+                Ok(answer) => answer,
+                Err(e) => format!("error: {}", e),
+            };
+
+            checked += 1;
+            if got != want {
+                failures.push(format!(
+                    "day {} part {}: got {}, expected {}",
+                    day, n, got, want
+                ));
+            }
+        }
+    }
+
+    eprintln!("checked {} answers", checked);
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
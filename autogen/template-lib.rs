@@ -0,0 +1,26 @@
+use common::ParseResult;
+
+pub fn part1(input: &str) -> ParseResult<u64> {
+    Ok(0)
+}
+
+pub fn part2(input: &str) -> ParseResult<u64> {
+    Ok(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/dayNN/example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE), Ok(0));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), Ok(0));
+    }
+}
@@ -1,23 +1,18 @@
-use clap::{arg, command, ArgAction};
+use clap::command;
+use common::cli::{self, Part};
+use dayN::{part1, part2};
 use std::io;
 
 fn main() -> Result<(), io::Error> {
-    let input = include_str!("../../input.txt");
-    let example = include_str!("../../example.txt");
-
     let matches = command!()
-        .arg(arg!(example: -e).action(ArgAction::SetTrue))
+        .args(cli::input_args())
+        .arg(cli::part_arg())
         .get_matches();
 
-    if matches.get_flag("example") {
-        println!("solution (example): {}", solution(example));
-    } else {
-        println!("solution: {}", solution(input));
-    }
+    let input = cli::read_input(DAY_NUM, &matches)?;
+    let part = *matches.get_one::<Part>("part").unwrap();
 
-    Ok(())
-}
+    cli::print_parts(part, &input, part1, part2);
 
-fn solution(input: &str) -> u64 {
-    0
+    Ok(())
 }
@@ -0,0 +1,155 @@
+use crate::error::ParseResult;
+use clap::builder::PossibleValue;
+use clap::{arg, Arg, ArgMatches, ValueEnum};
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// Directory holding the `dayNN/{input,example*}.txt` files
+pub const INPUTS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../inputs");
+
+/// A puzzle input and whether it is one of the examples
+pub struct Input {
+    /// where the input was read from, for error messages
+    pub name: String,
+    pub text: String,
+    pub example: bool,
+}
+
+/// Which part(s) of a puzzle to run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+    Both,
+}
+
+impl Part {
+    pub fn runs_part1(&self) -> bool {
+        matches!(self, Part::One | Part::Both)
+    }
+
+    pub fn runs_part2(&self) -> bool {
+        matches!(self, Part::Two | Part::Both)
+    }
+}
+
+impl ValueEnum for Part {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Part::One, Part::Two, Part::Both]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Part::One => PossibleValue::new("1"),
+            Part::Two => PossibleValue::new("2"),
+            Part::Both => PossibleValue::new("both"),
+        })
+    }
+}
+
+/// The `--part 1|2|both` argument, read back with `matches.get_one::<Part>("part")`
+pub fn part_arg() -> Arg {
+    arg!(part: -p --part <PART> "Which part of the puzzle to run")
+        .value_parser(clap::value_parser!(Part))
+        .default_value("both")
+}
+
+/// The `-e [N]` and `-i/--input <PATH>` arguments, read back with `read_input`
+pub fn input_args() -> [Arg; 2] {
+    [
+        arg!(example: -e --example [N] "Use the example input (inputs/dayNN/exampleN.txt)")
+            .value_parser(clap::value_parser!(u32)),
+        arg!(input: -i --input <PATH> "Read the input from PATH, or stdin if PATH is -")
+            .value_parser(clap::value_parser!(PathBuf))
+            .conflicts_with("example"),
+    ]
+}
+
+/// Path of the input for @day, or of example @n if @example. If the day only has one
+/// example, it is called `example.txt` instead of `example1.txt`.
+pub fn input_path(inputs: &Path, day: u32, example: Option<u32>) -> PathBuf {
+    let dir = inputs.join(format!("day{:02}", day));
+
+    match example {
+        Some(n) => {
+            let path = dir.join(format!("example{}.txt", n));
+
+            if n == 1 && !path.exists() {
+                dir.join("example.txt")
+            } else {
+                path
+            }
+        }
+        None => dir.join("input.txt"),
+    }
+}
+
+/// Reads the input for @day selected by the arguments from `input_args`
+pub fn read_input(day: u32, matches: &ArgMatches) -> io::Result<Input> {
+    if let Some(path) = matches.get_one::<PathBuf>("input") {
+        let mut text = String::new();
+        let name;
+
+        if path.as_os_str() == "-" {
+            io::stdin().read_to_string(&mut text)?;
+            name = String::from("<stdin>");
+        } else {
+            text = fs::read_to_string(path)?;
+            name = path.display().to_string();
+        }
+
+        return Ok(Input {
+            name,
+            text,
+            example: false,
+        });
+    }
+
+    let example = if matches.contains_id("example") {
+        Some(*matches.get_one::<u32>("example").unwrap_or(&1))
+    } else {
+        None
+    };
+
+    let path = input_path(Path::new(INPUTS_DIR), day, example);
+    let text = fs::read_to_string(&path)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+
+    Ok(Input {
+        name: path.display().to_string(),
+        text,
+        example: example.is_some(),
+    })
+}
+
+/// Prints the answer to part @n, or prints the parse error and exits if there was one
+pub fn print_answer<T: Display>(n: u32, input: &Input, answer: ParseResult<T>) {
+    let suffix = if input.example { " (example)" } else { "" };
+
+    match answer {
+        Ok(answer) => println!("part {}{}: {}", n, suffix, answer),
+        Err(e) => {
+            eprintln!("error: {}:{}", input.name, e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Runs and prints the parts of a puzzle selected by @part
+pub fn print_parts<T: Display, U: Display>(
+    part: Part,
+    input: &Input,
+    part1: impl FnOnce(&str) -> ParseResult<T>,
+    part2: impl FnOnce(&str) -> ParseResult<U>,
+) {
+    if part.runs_part1() {
+        print_answer(1, input, part1(&input.text));
+    }
+
+    if part.runs_part2() {
+        print_answer(2, input, part2(&input.text));
+    }
+}
@@ -0,0 +1,166 @@
+//! Finding where a sequence of states x0, f(x0), f(f(x0)), ... starts repeating, so
+//! a state far down the sequence can be found without stepping all the way there.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where a sequence starts repeating
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// index of the first state that comes round again (mu)
+    pub start: usize,
+    /// number of steps before it comes round (lambda)
+    pub len: usize,
+}
+
+impl Cycle {
+    /// Index of the earliest state that is the same as state @n
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+}
+
+/// Finds the cycle of the sequence starting at @start by remembering every state
+pub fn find_cycle<S: Hash + Eq + Clone>(start: S, step: impl FnMut(&S) -> S) -> Cycle {
+    match walk(start, step, None) {
+        Walk::Cycle(cycle, _) => cycle,
+        Walk::Reached(_) => unreachable!("only stops early with a limit"),
+    }
+}
+
+/// Finds the cycle of the sequence starting at @start with Brent's algorithm, which
+/// only keeps two states at a time but steps through the sequence about three times
+pub fn brent<S: Eq + Clone>(start: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    // find the length: the hare moves on while the tortoise waits at powers of 2
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+
+        hare = step(&hare);
+        len += 1;
+    }
+
+    // find the start: two states len apart meet for the first time at it
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..len {
+        hare = step(&hare);
+    }
+
+    let mut mu = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+
+    Cycle { start: mu, len }
+}
+
+/// The state @n steps after @start, found by stepping until either it is reached or
+/// the sequence repeats, and then skipping the whole cycles left
+pub fn nth<S: Hash + Eq + Clone>(start: S, step: impl FnMut(&S) -> S, n: usize) -> S {
+    match walk(start, step, Some(n)) {
+        Walk::Cycle(cycle, mut states) => states.swap_remove(cycle.reduce(n)),
+        Walk::Reached(state) => state,
+    }
+}
+
+/// The state @n steps after @start when the sequence has @cycle, which takes no more
+/// steps than getting round the cycle once
+pub fn jump<S>(start: S, mut step: impl FnMut(&S) -> S, n: usize, cycle: Cycle) -> S {
+    let mut state = start;
+
+    for _ in 0..cycle.reduce(n) {
+        state = step(&state);
+    }
+
+    state
+}
+
+enum Walk<S> {
+    /// the sequence repeated, with every state up to the repeat
+    Cycle(Cycle, Vec<S>),
+    /// the state at the limit, before anything repeated
+    Reached(S),
+}
+
+/// Steps from @start until a state repeats, or until @limit steps if there is one
+fn walk<S: Hash + Eq + Clone>(
+    start: S,
+    mut step: impl FnMut(&S) -> S,
+    limit: Option<usize>,
+) -> Walk<S> {
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = start;
+
+    loop {
+        if limit == Some(states.len()) {
+            return Walk::Reached(state);
+        } else if let Some(i) = seen.get(&state) {
+            let cycle = Cycle {
+                start: *i,
+                len: states.len() - i,
+            };
+
+            return Walk::Cycle(cycle, states);
+        }
+
+        let next = step(&state);
+        seen.insert(state.clone(), states.len());
+        states.push(state);
+        state = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 3 -> 7 -> 1 -> 4 -> 5 -> 1 -> ..., so the cycle starts at 2 and is 3 long
+    fn step(n: &u32) -> u32 {
+        match n {
+            3 => 7,
+            7 => 1,
+            1 => 4,
+            4 => 5,
+            _ => 1,
+        }
+    }
+
+    #[test]
+    fn both_agree() {
+        let cycle = Cycle { start: 2, len: 3 };
+
+        assert_eq!(find_cycle(3, step), cycle);
+        assert_eq!(brent(3, step), cycle);
+        assert_eq!(find_cycle(1, step), Cycle { start: 0, len: 3 });
+        assert_eq!(brent(5, step), Cycle { start: 0, len: 3 });
+    }
+
+    #[test]
+    fn skip_ahead() {
+        for n in [0, 1, 2, 3, 5, 1000, 1_000_000_000] {
+            let expected = [3, 7, 1, 4, 5][Cycle { start: 2, len: 3 }.reduce(n)];
+
+            assert_eq!(nth(3, step, n), expected);
+            assert_eq!(jump(3, step, n, brent(3, step)), expected);
+        }
+
+        // stops at the limit without looking for the cycle
+        assert_eq!(nth(0u64, |n| n + 1, 10), 10);
+    }
+}
@@ -0,0 +1,143 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// An error found while parsing a puzzle input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1 based line of the input the error is on
+    pub line: usize,
+    /// 1 based column of @token in the line
+    pub column: usize,
+    /// the offending token, empty if something was missing at the end of the line
+    pub token: String,
+    /// what should have been there instead
+    pub expected: String,
+}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+impl ParseError {
+    /// Creates an error for @token in @line, where @row is the 0 based index of @line in
+    /// the input. @token should be a slice of @line so its column can be worked out,
+    /// otherwise the error points at the start of the line.
+    pub fn new(row: usize, line: &str, token: &str, expected: impl Into<String>) -> Self {
+        let offset = (token.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+        let column = if offset <= line.len() && line.is_char_boundary(offset) {
+            line[..offset].chars().count() + 1
+        } else {
+            1
+        };
+
+        ParseError {
+            line: row + 1,
+            column,
+            token: token.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Creates an error for something missing at the end of @line
+    pub fn missing(row: usize, line: &str, expected: impl Into<String>) -> Self {
+        Self::new(row, line, &line[line.len()..], expected)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}: expected {}, ",
+            self.line, self.column, self.expected
+        )?;
+
+        if self.token.is_empty() {
+            write!(f, "found end of line")
+        } else {
+            write!(f, "found \"{}\"", self.token)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses @token, a slice of the @row'th @line, as a number
+pub fn parse_num<T: FromStr>(row: usize, line: &str, token: &str) -> ParseResult<T> {
+    token
+        .parse::<T>()
+        .map_err(|_| ParseError::new(row, line, token, "a number"))
+}
+
+/// Parses the whitespace separated numbers in @tokens, a slice of the @row'th @line
+pub fn parse_nums<T: FromStr>(row: usize, line: &str, tokens: &str) -> ParseResult<Vec<T>> {
+    tokens
+        .split_whitespace()
+        .map(|token| parse_num(row, line, token))
+        .collect()
+}
+
+/// Splits @line on the first @delim, erroring if it isn't there
+pub fn split_once<'a>(row: usize, line: &'a str, delim: &str) -> ParseResult<(&'a str, &'a str)> {
+    line.split_once(delim)
+        .ok_or_else(|| ParseError::missing(row, line, format!("\"{}\"", delim)))
+}
+
+/// Returns the lines of @input, checking that they all have the same length like the
+/// rows of a grid should
+pub fn grid_lines(input: &str) -> ParseResult<Vec<&str>> {
+    let lines: Vec<&str> = input.lines().collect();
+
+    if let Some(first) = lines.first() {
+        let width = first.chars().count();
+
+        for (row, line) in lines.iter().enumerate() {
+            let len = line.chars().count();
+
+            if len < width {
+                return Err(ParseError::missing(
+                    row,
+                    line,
+                    format!("{} characters", width),
+                ));
+            } else if len > width {
+                let extra = line.char_indices().nth(width).unwrap().0;
+
+                return Err(ParseError::new(row, line, &line[extra..], "end of line"));
+            }
+        }
+    }
+
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_of_token() {
+        let line = "Game 1: 3 blu";
+        let e = ParseError::new(4, line, &line[10..], "red, green or blue");
+
+        assert_eq!((e.line, e.column), (5, 11));
+        assert_eq!(
+            e.to_string(),
+            "5:11: expected red, green or blue, found \"blu\""
+        );
+    }
+
+    #[test]
+    fn missing_at_end_of_line() {
+        let e = split_once(0, "a b", ":").unwrap_err();
+
+        assert_eq!(e.column, 4);
+        assert_eq!(e.to_string(), "1:4: expected \":\", found end of line");
+    }
+
+    #[test]
+    fn ragged_grid() {
+        assert_eq!(grid_lines("..#\n.#.\n").unwrap(), vec!["..#", ".#."]);
+        assert_eq!(grid_lines("..#\n.#\n").unwrap_err().column, 3);
+        assert_eq!(grid_lines("..#\n.#..\n").unwrap_err().token, ".");
+    }
+}
@@ -0,0 +1,8 @@
+//! Code shared between the days and the `aoc` runner.
+
+pub mod cli;
+pub mod cycle;
+pub mod error;
+pub mod math;
+
+pub use error::{ParseError, ParseResult};
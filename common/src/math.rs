@@ -0,0 +1,75 @@
+//! Number theory for puzzles that come down to things lining up periodically.
+
+pub fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+pub fn lcm(a: u64, b: u64) -> u64 {
+    a / gcd(a, b) * b
+}
+
+/// Returns (g, x, y) with a*x + b*y = g = gcd(a, b)
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+
+        (g, y, x - (a / b) * y)
+    }
+}
+
+/// Solves x = r (mod m) for every (r, m) in @congruences, where the moduli don't have
+/// to be coprime. Returns the smallest solution and the lcm of the moduli, which every
+/// other solution differs from it by a multiple of. None if the congruences contradict
+/// each other or the lcm doesn't fit in an i128.
+pub fn crt(congruences: &[(u64, u64)]) -> Option<(u128, u128)> {
+    let (mut x, mut m): (i128, i128) = (0, 1);
+
+    for &(r, n) in congruences {
+        assert!(n > 0, "modulus can't be 0");
+        let (r, n) = (r as i128 % n as i128, n as i128);
+
+        // x + m*k = r (mod n)  <=>  m*k = r - x (mod n)
+        let (g, inv, _) = extended_gcd(m, n);
+        let diff = r - x;
+
+        if diff % g != 0 {
+            return None;
+        }
+
+        // both factors are below n / g, so their product fits in a u128
+        let n_g = n / g;
+        let k = (diff / g).rem_euclid(n_g) as u128 * inv.rem_euclid(n_g) as u128;
+        let k = (k % n_g as u128) as i128;
+
+        x = x.checked_add(m.checked_mul(k)?)?;
+        m = m.checked_mul(n_g)?;
+        x = x.rem_euclid(m);
+    }
+
+    Some((x as u128, m as u128))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coprime() {
+        // x = 2 (mod 3), x = 3 (mod 5), x = 2 (mod 7)
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    }
+
+    #[test]
+    fn not_coprime() {
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(0, 6), (0, 4), (0, 10)]), Some((0, 60)));
+        assert_eq!(lcm(6, 4), 12);
+    }
+}
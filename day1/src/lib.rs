@@ -0,0 +1,79 @@
+use common::{ParseError, ParseResult};
+
+fn check_line(line: &str) -> String {
+    let words = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+    let mut ret = line.to_string();
+
+    for (w, d) in words.iter().zip(1..=9) {
+        let match_str = ret.clone();
+
+        for (i, (mi, _)) in match_str.match_indices(w).enumerate() {
+            ret.insert(mi + i + 1, char::from_digit(d, 10).unwrap());
+        }
+    }
+
+    ret
+}
+
+/// Checks that the lines of @input only hold letters and digits
+fn check_input(input: &str) -> ParseResult<()> {
+    for (row, line) in input.lines().enumerate() {
+        if let Some(bad) = line.matches(|c: char| !c.is_ascii_alphanumeric()).next() {
+            return Err(ParseError::new(row, line, bad, "a letter or digit"));
+        }
+    }
+
+    Ok(())
+}
+
+fn calibration_sum(lines: impl Iterator<Item = String>) -> u32 {
+    let mut nums = Vec::new();
+
+    for c in lines {
+        let digits: Vec<u32> = c
+            .chars()
+            .filter(|c| c.is_numeric())
+            .map(|c| c.to_digit(10).unwrap())
+            .collect();
+
+        if digits.is_empty() {
+            continue;
+        }
+
+        nums.push(digits.first().unwrap() * 10 + digits.last().unwrap());
+    }
+
+    nums.iter().sum()
+}
+
+pub fn part1(input: &str) -> ParseResult<u32> {
+    check_input(input)?;
+
+    Ok(calibration_sum(input.lines().map(|l| l.to_string())))
+}
+
+pub fn part2(input: &str) -> ParseResult<u32> {
+    check_input(input)?;
+
+    Ok(calibration_sum(input.lines().map(check_line)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE1: &str = include_str!("../../inputs/day01/example1.txt");
+    const EXAMPLE2: &str = include_str!("../../inputs/day01/example2.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE1), Ok(142));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE2), Ok(281));
+    }
+}
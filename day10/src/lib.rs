@@ -0,0 +1,217 @@
+use crate::pipe::*;
+use common::{ParseError, ParseResult};
+use grid::Grid;
+
+mod pipe;
+
+/// Components of the pipe @c, see `Pipe`
+fn components(c: char) -> Option<[bool; 4]> {
+    match c {
+        '|' => Some([true, true, false, false]),
+        '-' => Some([false, false, true, true]),
+        'L' => Some([true, false, true, false]),
+        'J' => Some([true, false, false, true]),
+        '7' => Some([false, true, false, true]),
+        'F' => Some([false, true, true, false]),
+        '.' | 'S' => Some([false; 4]),
+        _ => None,
+    }
+}
+
+/// The loop of pipes through the start
+#[derive(Debug)]
+pub struct Loop {
+    pipes: Grid<Pipe>,
+    /// every pipe of the loop in order, starting from the start
+    path: Vec<(usize, usize)>,
+    on_loop: Grid<bool>,
+}
+
+impl Loop {
+    /// Parses the pipes and traces the loop that goes through the start
+    pub fn parse(input: &str) -> ParseResult<Self> {
+        let chars = Grid::parse(input, "a pipe", |c| components(c).map(|_| c))?;
+        let mut starts = chars.iter().filter(|(_, c)| **c == 'S').map(|(p, _)| p);
+
+        let start_pos = starts.next().ok_or_else(|| {
+            let (row, line) = input.lines().enumerate().last().unwrap_or((0, ""));
+
+            ParseError::missing(row, line, "a start \"S\"")
+        })?;
+        let start_line = input.lines().nth(start_pos.0).unwrap();
+        let start_token = &start_line[start_pos.1..=start_pos.1];
+
+        if let Some((r, c)) = starts.next() {
+            let line = input.lines().nth(r).unwrap();
+
+            return Err(ParseError::new(r, line, &line[c..=c], "only one start"));
+        }
+
+        let mut pipes = Grid::from_vec(
+            chars.width(),
+            chars
+                .iter()
+                .map(|(pos, c)| Pipe::new(pos, components(*c).unwrap(), *c == 'S'))
+                .collect(),
+        );
+        pipes[start_pos] = find_start_kind(start_pos, &pipes);
+
+        let path = (pipes[start_pos].connections(&pipes).len() == 2)
+            .then(|| trace_loop(start_pos, &pipes))
+            .flatten()
+            .ok_or_else(|| {
+                ParseError::new(
+                    start_pos.0,
+                    start_line,
+                    start_token,
+                    "a start on a loop, connected to exactly two pipes",
+                )
+            })?;
+
+        let mut on_loop = Grid::new(pipes.width(), pipes.height(), false);
+        for pos in &path {
+            on_loop[*pos] = true;
+        }
+
+        Ok(Loop {
+            pipes,
+            path,
+            on_loop,
+        })
+    }
+
+    /// Pipes of the loop in order, starting from the start
+    pub fn path(&self) -> &[(usize, usize)] {
+        &self.path
+    }
+
+    /// Steps to the pipe of the loop farthest from the start
+    pub fn farthest(&self) -> usize {
+        self.path.len() / 2
+    }
+
+    /// Whether every tile of @row is inside the loop, going along the row and flipping
+    /// at every pipe of the loop that goes down from it
+    fn inside_row(&self, row: usize) -> Vec<bool> {
+        let mut inside = false;
+
+        self.pipes
+            .row(row)
+            .iter()
+            .zip(self.on_loop.row(row))
+            .map(|(p, on_loop)| {
+                if *on_loop {
+                    inside ^= p.is_intersection();
+                    false
+                } else {
+                    inside
+                }
+            })
+            .collect()
+    }
+
+    /// Tiles enclosed by the loop, counted by scanning each row
+    pub fn enclosed_scanline(&self) -> usize {
+        (0..self.pipes.height())
+            .map(|r| self.inside_row(r).into_iter().filter(|i| *i).count())
+            .sum()
+    }
+
+    /// Tiles enclosed by the loop, from its area by the shoelace formula and Pick's
+    /// theorem: area = inside + boundary / 2 - 1
+    pub fn enclosed_shoelace(&self) -> usize {
+        let twice_area = self
+            .path
+            .iter()
+            .zip(self.path.iter().cycle().skip(1))
+            .map(|(a, b)| a.0 as i64 * b.1 as i64 - b.0 as i64 * a.1 as i64)
+            .sum::<i64>()
+            .unsigned_abs() as usize;
+
+        (twice_area + 2 - self.path.len()) / 2
+    }
+
+    /// The loop drawn with box drawing characters and the enclosed tiles as "O", one
+    /// line per row prefixed by its number
+    pub fn render(&self) -> String {
+        let mut s = String::new();
+
+        for r in 0..self.pipes.height() {
+            s += &format!("{:4}: ", r);
+
+            for (c, inside) in self.inside_row(r).into_iter().enumerate() {
+                if self.on_loop[(r, c)] {
+                    s += &self.pipes[(r, c)].to_string();
+                } else if inside {
+                    s.push('O');
+                } else {
+                    s.push(' ');
+                }
+            }
+
+            s.push('\n');
+        }
+
+        s
+    }
+}
+
+pub fn part1(input: &str) -> ParseResult<usize> {
+    Ok(Loop::parse(input)?.farthest())
+}
+
+pub fn part2(input: &str) -> ParseResult<usize> {
+    Ok(Loop::parse(input)?.enclosed_scanline())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE1: &str = include_str!("../../inputs/day10/example1.txt");
+    const EXAMPLE2: &str = include_str!("../../inputs/day10/example2.txt");
+    const EXAMPLE3: &str = include_str!("../../inputs/day10/example3.txt");
+    const EXAMPLE4: &str = include_str!("../../inputs/day10/example4.txt");
+    const EXAMPLE5: &str = include_str!("../../inputs/day10/example5.txt");
+
+    #[test]
+    fn part1_example1() {
+        assert_eq!(part1(EXAMPLE1), Ok(4));
+    }
+
+    #[test]
+    fn part1_example2() {
+        assert_eq!(part1(EXAMPLE2), Ok(8));
+    }
+
+    #[test]
+    fn part2_example3() {
+        assert_eq!(part2(EXAMPLE3), Ok(4));
+    }
+
+    #[test]
+    fn part2_example4() {
+        assert_eq!(part2(EXAMPLE4), Ok(8));
+    }
+
+    #[test]
+    fn part2_example5() {
+        assert_eq!(part2(EXAMPLE5), Ok(10));
+    }
+
+    #[test]
+    fn shoelace_matches_scanline() {
+        for example in [EXAMPLE1, EXAMPLE2, EXAMPLE3, EXAMPLE4, EXAMPLE5] {
+            let l = Loop::parse(example).unwrap();
+
+            assert_eq!(l.enclosed_shoelace(), l.enclosed_scanline());
+        }
+    }
+
+    #[test]
+    fn broken_loop() {
+        let e = Loop::parse("S-7\n|.|\nL-.\n").unwrap_err();
+
+        assert_eq!((e.line, e.column), (1, 1));
+    }
+}
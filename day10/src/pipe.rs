@@ -1,3 +1,4 @@
+use grid::Grid;
 use std::fmt::Display;
 
 #[derive(Debug, Clone)]
@@ -5,10 +6,7 @@ pub struct Pipe {
     pos: (usize, usize),
     /// Components are in order of: N,S,E,W
     components: [bool; 4],
-    step_cnt: usize,
-    edges: Vec<(usize, usize)>,
     is_start: bool,
-    marked: bool,
 }
 
 impl Display for Pipe {
@@ -37,17 +35,10 @@ impl Pipe {
         Pipe {
             pos,
             components,
-            step_cnt: usize::MAX,
-            edges: Vec::new(),
             is_start,
-            marked: false,
         }
     }
 
-    pub fn is_marked(&self) -> bool {
-        self.marked
-    }
-
     pub fn is_intersection(&self) -> bool {
         match self.components {
             // NS "|"
@@ -60,115 +51,59 @@ impl Pipe {
         }
     }
 
-    #[allow(unused)]
-    pub fn get_steps(&self) -> usize {
-        self.step_cnt
+    /// Neighbours this pipe connects to that connect back to it
+    pub fn connections(&self, pipes: &Grid<Pipe>) -> Vec<(usize, usize)> {
+        CHECK_ORDER
+            .into_iter()
+            .filter(|i| self.components[*i])
+            .filter_map(|i| pipes.offset(self.pos, OFFSETS[i]))
+            .filter(|p| pipes[*p].components[opposite(self.pos, *p)])
+            .collect()
     }
+}
 
-    pub fn find_connections(&mut self, pipes: &Vec<Vec<Pipe>>) {
-        let (r, c) = (self.pos.0, self.pos.1);
-        let mut surround = Vec::new();
-
-        if r > 0 {
-            let other = &pipes[r - 1][c];
-            if self.components[0] && other.components[1] {
-                surround.push(pipes[r - 1][c].pos);
-            }
-        }
-
-        if r < pipes.len() - 1 {
-            let other = &pipes[r + 1][c];
-
-            if self.components[1] && other.components[0] {
-                surround.push(pipes[r + 1][c].pos);
-            }
-        }
-
-        if c > 0 {
-            let other = &pipes[r][c - 1];
-            if self.components[3] && other.components[2] {
-                surround.push(pipes[r][c - 1].pos);
-            }
-        }
+/// (row, column) offsets to the neighbours in the order of `Pipe::components`
+const OFFSETS: [(i64, i64); 4] = [(-1, 0), (1, 0), (0, 1), (0, -1)];
 
-        if c < pipes[0].len() - 1 {
-            let other = &pipes[r][c + 1];
+/// Order the neighbours are checked in: N, S, W, E
+const CHECK_ORDER: [usize; 4] = [0, 1, 3, 2];
 
-            if self.components[2] && other.components[3] {
-                surround.push(pipes[r][c + 1].pos);
-            }
-        }
+/// Index of the component of the pipe at @to that points back at @from
+fn opposite(from: (usize, usize), to: (usize, usize)) -> usize {
+    let offset = (from.0 as i64 - to.0 as i64, from.1 as i64 - to.1 as i64);
 
-        self.edges = surround;
-    }
+    OFFSETS.iter().position(|o| *o == offset).unwrap()
 }
 
-pub fn find_start_kind(start: (usize, usize), pipes: &Vec<Vec<Pipe>>) -> Pipe {
-    let (r, c) = start;
+pub fn find_start_kind(start: (usize, usize), pipes: &Grid<Pipe>) -> Pipe {
     let mut connections = [false, false, false, false];
 
-    if r > 0 {
-        let other = &pipes[r - 1][c];
-        if other.components[1] {
-            connections[0] = true;
-        }
-    }
-
-    if r < pipes.len() - 1 {
-        let other = &pipes[r + 1][c];
-
-        if other.components[0] {
-            connections[1] = true;
-        }
-    }
-
-    if c > 0 {
-        let other = &pipes[r][c - 1];
-        if other.components[2] {
-            connections[3] = true;
-        }
-    }
-
-    if c < pipes[0].len() - 1 {
-        let other = &pipes[r][c + 1];
-
-        if other.components[3] {
-            connections[2] = true;
+    for (i, offset) in OFFSETS.iter().enumerate() {
+        if let Some(p) = pipes.offset(start, *offset) {
+            connections[i] = pipes[p].components[opposite(start, p)];
         }
     }
 
     Pipe::new(start, connections, true)
 }
 
-pub fn traverse_loop(
-    start: (usize, usize),
-    last: (usize, usize),
-    steps: usize,
-    pipes: &mut Vec<Vec<Pipe>>,
-) {
-    let (sr, sc) = start;
-    let (lr, lc) = last;
-
-    if sr >= pipes.len() || sc >= pipes[0].len() || lr >= pipes.len() || lc >= pipes[0].len() {
-        return;
+/// Follows the pipes from @start until they lead back to it, returning every pipe of
+/// the loop in order. None if the pipes don't lead back to the start.
+pub fn trace_loop(start: (usize, usize), pipes: &Grid<Pipe>) -> Option<Vec<(usize, usize)>> {
+    let mut path = vec![start];
+    let mut last = start;
+    let mut pos = *pipes[start].connections(pipes).first()?;
+
+    while pos != start {
+        let next = pipes[pos]
+            .connections(pipes)
+            .into_iter()
+            .find(|p| *p != last)?;
+
+        path.push(pos);
+        last = pos;
+        pos = next;
     }
 
-    let pipe = &mut pipes[sr][sc];
-
-    if pipe.components.iter().all(|c| !*c) || (pipe.is_start && steps > 0) {
-        return;
-    }
-
-    pipe.marked = true;
-
-    if steps >= pipe.step_cnt {
-        return;
-    }
-
-    pipe.step_cnt = steps;
-
-    let edges = pipe.edges.clone();
-    for e in edges.iter().copied() {
-        traverse_loop(e, start, steps + 1, pipes);
-    }
+    Some(path)
 }
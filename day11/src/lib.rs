@@ -0,0 +1,131 @@
+use common::ParseResult;
+use grid::{Grid, Point};
+
+/// How many rows/columns every empty one becomes in part 2
+pub const PART2_EXPANSION: u64 = 1_000_000;
+
+/// Expanded coordinate of every row (or column) of the image, where @empty tells which
+/// are empty and each of those becomes @expansion rows. Keeps a running count of the
+/// empty rows so far instead of shifting the galaxies once per empty row.
+fn expanded_coords(empty: impl Iterator<Item = bool>, expansion: u64) -> Vec<u64> {
+    let mut empty_before = 0;
+
+    empty
+        .enumerate()
+        .map(|(i, empty)| {
+            let coord = i as u64 + empty_before * (expansion - 1);
+            empty_before += empty as u64;
+            coord
+        })
+        .collect()
+}
+
+/// (row, column) of every galaxy in @input after every empty row and column has been
+/// replaced by @expansion empty rows/columns
+pub fn galaxies(input: &str, expansion: u64) -> ParseResult<Vec<(u64, u64)>> {
+    let image = Grid::parse(input, "\".\" or \"#\"", |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+
+    let rows = expanded_coords(
+        (0..image.height()).map(|r| !image.row(r).contains(&true)),
+        expansion,
+    );
+    let columns = expanded_coords(
+        (0..image.width()).map(|c| !image.column(c).any(|g| *g)),
+        expansion,
+    );
+
+    Ok(image
+        .iter()
+        .filter(|(_, g)| **g)
+        .map(|((r, c), _)| (rows[r], columns[c]))
+        .collect())
+}
+
+/// Sum of the distances between every pair of @coords on a line. Once sorted, each
+/// coordinate is that far past every coordinate before it.
+fn sum_axis(mut coords: Vec<u64>) -> u64 {
+    coords.sort_unstable();
+
+    let mut before = 0;
+    let mut sum = 0;
+
+    for (i, c) in coords.into_iter().enumerate() {
+        sum += c * i as u64 - before;
+        before += c;
+    }
+
+    sum
+}
+
+/// Sum of the Manhattan distances between every pair of @galaxies in O(n log n), which
+/// is the sum of the distances along each axis on its own
+pub fn sum_pairwise(galaxies: &[(u64, u64)]) -> u64 {
+    sum_axis(galaxies.iter().map(|g| g.0).collect())
+        + sum_axis(galaxies.iter().map(|g| g.1).collect())
+}
+
+/// `sum_pairwise` by measuring every pair, O(n^2)
+pub fn sum_pairwise_naive(galaxies: &[(u64, u64)]) -> u64 {
+    let points: Vec<Point> = galaxies
+        .iter()
+        .map(|(r, c)| Point::new(*c as i64, *r as i64))
+        .collect();
+    let mut sum = 0;
+
+    for (i, a) in points.iter().enumerate() {
+        for b in &points[i + 1..] {
+            sum += a.manhattan(*b);
+        }
+    }
+
+    sum
+}
+
+/// Sum of the distances between every pair of galaxies after every empty row and
+/// column has been replaced by @expansion empty rows/columns
+pub fn sum_distances(input: &str, expansion: u64) -> ParseResult<u64> {
+    Ok(sum_pairwise(&galaxies(input, expansion)?))
+}
+
+pub fn part1(input: &str) -> ParseResult<u64> {
+    sum_distances(input, 2)
+}
+
+pub fn part2(input: &str) -> ParseResult<u64> {
+    sum_distances(input, PART2_EXPANSION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day11/example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE), Ok(374));
+    }
+
+    #[test]
+    fn expansion_10_example() {
+        assert_eq!(sum_distances(EXAMPLE, 10), Ok(1030));
+    }
+
+    #[test]
+    fn expansion_100_example() {
+        assert_eq!(sum_distances(EXAMPLE, 100), Ok(8410));
+    }
+
+    #[test]
+    fn matches_naive() {
+        for expansion in [1, 2, 10, PART2_EXPANSION] {
+            let g = galaxies(EXAMPLE, expansion).unwrap();
+
+            assert_eq!(sum_pairwise(&g), sum_pairwise_naive(&g));
+        }
+    }
+}
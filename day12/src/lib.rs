@@ -0,0 +1,251 @@
+use common::error::{parse_num, split_once};
+use common::{ParseError, ParseResult};
+use std::fmt::Display;
+
+/// A row of springs, each operational ('.'), damaged ('#') or unknown ('?'), and the
+/// sizes of the groups of damaged springs in it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    springs: String,
+    groups: Vec<usize>,
+}
+
+impl Record {
+    pub fn parse(row: usize, line: &str) -> ParseResult<Self> {
+        let (springs, groups) = split_once(row, line, " ")?;
+
+        if let Some(bad) = springs.matches(|c| !".#?".contains(c)).next() {
+            return Err(ParseError::new(row, line, bad, "\".\", \"#\" or \"?\""));
+        }
+
+        let groups = groups
+            .split(',')
+            .map(|n| parse_num(row, line, n))
+            .collect::<ParseResult<Vec<usize>>>()?;
+
+        Ok(Record {
+            springs: springs.to_string(),
+            groups,
+        })
+    }
+
+    /// The record repeated @factor times, with an unknown spring between the copies
+    pub fn unfolded(&self, factor: usize) -> Record {
+        Record {
+            springs: vec![self.springs.as_str(); factor].join("?"),
+            groups: self.groups.repeat(factor),
+        }
+    }
+
+    /// The springs with an operational one added at both ends, so every group has a
+    /// gap before and after it
+    fn padded(&self) -> Vec<char> {
+        format!(".{}.", self.springs).chars().collect()
+    }
+
+    /// Whether each spring of a match is damaged: a gap, then each group followed
+    /// by a gap
+    fn states(&self) -> Vec<bool> {
+        let mut bools = vec![false];
+
+        for g in &self.groups {
+            bools.append(&mut vec![true; *g]);
+
+            bools.push(false);
+        }
+
+        bools
+    }
+
+    /*
+     * from here: https://www.reddit.com/r/adventofcode/comments/18ge41g/2023_day_12_solutions/kd18cl9/
+     * comments by me :)
+     */
+    /// Table of the arrangements of the rest of the springs from every spring and
+    /// state, None if a count doesn't fit in a u64
+    fn table(&self) -> Option<Vec<Vec<u64>>> {
+        let springs = self.padded();
+        let groups = self.states();
+        // dp[i][j] == [character c][possible states of c]
+        let mut dp = vec![vec![0u64; groups.len() + 1]; springs.len() + 1];
+
+        // "base" case
+        dp[springs.len()][groups.len()] = 1;
+
+        // compare spring ('#', '.', or '?') to group[j] ('T', 'F")
+        for (i, c) in springs.iter().enumerate().rev() {
+            for j in (0..groups.len()).rev() {
+                // set what possible state the character could have
+                let (damaged, operational) = match c {
+                    '#' => (true, false),
+                    '.' => (false, true),
+                    _ => (true, true),
+                };
+
+                // this character is the sum of possibilites based on the
+                // 1-2 characters before it
+                dp[i][j] = if damaged && groups[j] {
+                    // if this character could be damaged then we must be in a group
+                    dp[i + 1][j + 1]
+                } else if operational && !groups[j] {
+                    // if this character is operational then we must be in a gap of
+                    // operational springs
+                    dp[i + 1][j + 1].checked_add(dp[i + 1][j])?
+                } else {
+                    0
+                };
+            }
+        }
+
+        Some(dp)
+    }
+
+    /// Number of ways the unknown springs can be filled in to match the groups, None
+    /// if it doesn't fit in a u64
+    pub fn count(&self) -> Option<u64> {
+        Some(self.table()?[0][0])
+    }
+
+    /// The @n-th way (0 based) of filling in the unknown springs, found by walking down
+    /// the table and skipping over the branches with fewer arrangements than are left
+    /// of @n. None if there aren't that many.
+    pub fn nth_arrangement(&self, mut n: u64) -> Option<String> {
+        let dp = self.table()?;
+        let springs = self.padded();
+        let groups = self.states();
+        let mut arrangement = String::new();
+        let mut j = 0;
+
+        if n >= dp[0][0] {
+            return None;
+        }
+
+        // the table already rules out every state that can't lead to a match, so the
+        // spring at i is damaged exactly when the state is
+        for i in 0..springs.len() {
+            if (1..springs.len() - 1).contains(&i) {
+                arrangement.push(if groups[j] { '#' } else { '.' });
+            }
+
+            // a gap can go on or end here, the groups can't
+            if !groups[j] && n < dp[i + 1][j] {
+                continue;
+            } else if !groups[j] {
+                n -= dp[i + 1][j];
+            }
+
+            j += 1;
+        }
+
+        Some(arrangement)
+    }
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let groups: Vec<String> = self.groups.iter().map(|g| g.to_string()).collect();
+
+        write!(f, "{} {}", self.springs, groups.join(","))
+    }
+}
+
+/// Every record in @input with the 0 based row it is on
+pub fn parse(input: &str) -> ParseResult<Vec<(usize, Record)>> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(row, line)| Ok((row, Record::parse(row, line)?)))
+        .collect()
+}
+
+/// The arrangements of every line of @input after unfolding it @unfold times, with
+/// the 0 based row it is on
+pub fn line_counts(input: &str, unfold: usize) -> ParseResult<Vec<(usize, u64)>> {
+    parse(input)?
+        .into_iter()
+        .map(|(row, record)| {
+            let count = record.unfolded(unfold).count().ok_or_else(|| {
+                let line = input.lines().nth(row).unwrap();
+
+                ParseError::new(row, line, line, "fewer than 2^64 arrangements")
+            })?;
+
+            Ok((row, count))
+        })
+        .collect()
+}
+
+/// Sums the arrangements of every line after unfolding it @unfold times
+pub fn count_arrangements(input: &str, unfold: usize) -> ParseResult<u64> {
+    let mut sum: u64 = 0;
+
+    for (row, count) in line_counts(input, unfold)? {
+        sum = sum.checked_add(count).ok_or_else(|| {
+            let line = input.lines().nth(row).unwrap();
+
+            ParseError::new(row, line, line, "fewer than 2^64 arrangements in total")
+        })?;
+    }
+
+    Ok(sum)
+}
+
+pub fn part1(input: &str) -> ParseResult<u64> {
+    count_arrangements(input, 1)
+}
+
+pub fn part2(input: &str) -> ParseResult<u64> {
+    count_arrangements(input, 5)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day12/example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE), Ok(21));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), Ok(525152));
+    }
+
+    #[test]
+    fn per_line() {
+        let counts: Vec<u64> = line_counts(EXAMPLE, 1)
+            .unwrap()
+            .into_iter()
+            .map(|(_, c)| c)
+            .collect();
+
+        assert_eq!(counts, [1, 4, 1, 1, 4, 10]);
+        assert_eq!(
+            count_arrangements(EXAMPLE, 2).unwrap(),
+            1 + 32 + 1 + 2 + 20 + 150
+        );
+    }
+
+    #[test]
+    fn arrangements() {
+        let record = Record::parse(0, "?###???????? 3,2,1").unwrap();
+        let all: Vec<String> = (0..10)
+            .map(|n| record.nth_arrangement(n).unwrap())
+            .collect();
+
+        assert!(all.contains(&".###.##.#...".to_string()));
+        assert!(all.contains(&".###....##.#".to_string()));
+        assert_eq!(record.nth_arrangement(10), None);
+
+        for (i, a) in all.iter().enumerate() {
+            let fixed = Record::parse(0, &format!("{} 3,2,1", a)).unwrap();
+
+            assert_eq!(fixed.count(), Some(1));
+            assert!(!all[..i].contains(a));
+        }
+    }
+}
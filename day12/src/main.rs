@@ -1,111 +1,67 @@
 use clap::{arg, command, ArgAction};
+use common::cli::{self, Part};
+use day12::{line_counts, parse, part1, part2};
 use std::io;
 
 fn main() -> Result<(), io::Error> {
-    let input = include_str!("../../input.txt");
-    let example = include_str!("../../example.txt");
-
     let matches = command!()
-        .arg(arg!(example: -e).action(ArgAction::SetTrue))
+        .args(cli::input_args())
+        .arg(cli::part_arg())
+        .arg(
+            arg!(unfold: --unfold <N> "Run once with every record unfolded N times (1 is part 1, 5 part 2)")
+                .value_parser(clap::value_parser!(u64).range(1..)),
+        )
+        .arg(
+            arg!(lines: --lines "Print the arrangements of every line instead of the sum")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            arg!(arrangements: --arrangements <N> "With --lines, also print up to N of the arrangements of every line, spread evenly over all of them")
+                .value_parser(clap::value_parser!(u64))
+                .requires("lines"),
+        )
         .get_matches();
 
-    if matches.get_flag("example") {
-        println!("solution (example): {}", solution(example));
-    } else {
-        println!("solution: {}", solution(input));
-    }
-
-    Ok(())
-}
-
-/*
- * from here: https://www.reddit.com/r/adventofcode/comments/18ge41g/2023_day_12_solutions/kd18cl9/
- * comments by me :)
- */
-fn find_arrangements(springs: &str, groups: &Vec<bool>) -> u64 {
-    let mut dp: Vec<Vec<u64>> = Vec::new();
-    // dp[i][j] == [character c][possible states of c]
-    dp.resize(springs.len() + 1, Vec::new());
+    let input = cli::read_input(12, &matches)?;
+    let part = *matches.get_one::<Part>("part").unwrap();
+    let unfold = matches.get_one::<u64>("unfold").map(|n| *n as usize);
+
+    if !matches.get_flag("lines") {
+        match unfold {
+            Some(unfold) => match day12::count_arrangements(&input.text, unfold) {
+                Ok(sum) => println!("arrangements: {}", sum),
+                Err(e) => cli::print_answer::<u64>(1, &input, Err(e)),
+            },
+            None => cli::print_parts(part, &input, part1, part2),
+        }
 
-    for r in dp.iter_mut() {
-        r.resize(groups.len() + 1, 0);
+        return Ok(());
     }
 
-    // "base" case
-    dp[springs.len()][groups.len()] = 1;
-
-    // compare spring ('#', '.', or '?') to group[j] ('T', 'F")
-    for (i, c) in springs.char_indices().rev() {
-        for j in (0..groups.len()).rev() {
-            let (mut damaged, mut operational) = (false, false);
-
-            // set what possible state the character could have
-            match c {
-                '#' => damaged = true,
-                '.' => operational = true,
-                _ => {
-                    damaged = true;
-                    operational = true
-                }
-            }
-
-            let mut sum = 0;
-
-            // if this character could be damaged then we must be in a group
-            if damaged && groups[j] {
-                sum += dp[i + 1][j + 1];
-            // if this character is operational then we must be in a gap of
-            // operational springs
-            } else if operational && !groups[j] {
-                sum += dp[i + 1][j + 1] + dp[i + 1][j];
-            }
-
-            // this character is the sum of possibilites based on the
-            // 1-2 characters before it
-            dp[i][j] = sum;
+    // one count per line, "line: count", so the output can be diffed
+    let unfold = unfold.unwrap_or(if part.runs_part1() { 1 } else { 5 });
+    let counts = match line_counts(&input.text, unfold) {
+        Ok(counts) => counts,
+        Err(e) => {
+            cli::print_answer::<u64>(1, &input, Err(e));
+            return Ok(());
         }
-    }
-
-    dp[0][0]
-}
-
-fn make_groups(groups: Vec<usize>) -> Vec<bool> {
-    let mut bools = Vec::new();
-
-    bools.push(false);
-
-    for g in groups {
-        bools.append(&mut vec![true; g]);
+    };
+    let records = parse(&input.text).unwrap();
+    let samples = matches.get_one::<u64>("arrangements").copied().unwrap_or(0);
 
-        bools.push(false);
-    }
-
-    bools
-}
+    for ((row, count), (_, record)) in counts.into_iter().zip(records) {
+        println!("{}: {}", row + 1, count);
 
-fn solution(input: &str) -> u64 {
-    let lines = input.lines();
-    let mut sum = 0;
+        let record = record.unfolded(unfold);
+        let samples = samples.min(count);
 
-    for line in lines {
-        let parts: Vec<_> = line.split(' ').collect();
+        for k in 0..samples {
+            let n = (k as u128 * count as u128 / samples as u128) as u64;
 
-        if parts.len() != 2 {
-            continue;
+            println!("    {}", record.nth_arrangement(n).unwrap());
         }
-
-        let mut springs = (parts[0].to_string() + "?").repeat(5);
-        springs.pop();
-        springs = ".".to_string() + &springs + ".";
-        let groups: Vec<_> = (parts[1].to_string() + ",")
-            .repeat(5)
-            .split(',')
-            .filter(|s| !s.is_empty())
-            .map(|n| n.parse::<usize>().unwrap())
-            .collect();
-
-        sum += find_arrangements(&springs.to_string(), &make_groups(groups));
     }
 
-    sum
+    Ok(())
 }
@@ -0,0 +1,245 @@
+use common::{ParseError, ParseResult};
+use std::fmt::Display;
+
+/// Rows and columns are bitmasks, so neither can be longer than this
+const MAX_SIZE: usize = u64::BITS as usize;
+
+/// Which way a line of reflection runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    /// between two rows
+    Horizontal,
+    /// between two columns
+    Vertical,
+}
+
+/// A line of reflection, with the number of rows above or columns left of it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reflection {
+    pub axis: Axis,
+    pub before: usize,
+}
+
+impl Reflection {
+    /// The puzzle's summary of the line: columns to the left, or 100 times the rows above
+    pub fn score(&self) -> u64 {
+        match self.axis {
+            Axis::Horizontal => self.before as u64 * 100,
+            Axis::Vertical => self.before as u64,
+        }
+    }
+}
+
+impl Display for Reflection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.axis {
+            Axis::Horizontal => write!(f, "horizontal below row {}", self.before),
+            Axis::Vertical => write!(f, "vertical after column {}", self.before),
+        }
+    }
+}
+
+/// A pattern of ash ('.') and rocks ('#'), with every row and every column stored as a
+/// bitmask of where the rocks are
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    /// 1 based line of the input the pattern starts on
+    pub line: usize,
+    rows: Vec<u64>,
+    columns: Vec<u64>,
+}
+
+impl Pattern {
+    fn new(line: usize, rows: &[&str]) -> Self {
+        let bits = |cells: &mut dyn Iterator<Item = bool>| {
+            cells
+                .enumerate()
+                .fold(0, |mask, (i, rock)| mask | (rock as u64) << i)
+        };
+
+        Pattern {
+            line,
+            rows: rows
+                .iter()
+                .map(|r| bits(&mut r.bytes().map(|b| b == b'#')))
+                .collect(),
+            columns: (0..rows[0].len())
+                .map(|c| bits(&mut rows.iter().map(|r| r.as_bytes()[c] == b'#')))
+                .collect(),
+        }
+    }
+
+    /// Every line of reflection where exactly @smudges cells differ from their mirror
+    /// image, horizontal ones first
+    pub fn reflections(&self, smudges: u32) -> Vec<Reflection> {
+        let horizontal = mirror_lines(&self.rows, smudges).map(|before| Reflection {
+            axis: Axis::Horizontal,
+            before,
+        });
+        let vertical = mirror_lines(&self.columns, smudges).map(|before| Reflection {
+            axis: Axis::Vertical,
+            before,
+        });
+
+        horizontal.chain(vertical).collect()
+    }
+}
+
+/// Every place between two of @lines where the lines mirror each other with exactly
+/// @smudges cells different, as the number of lines before it
+fn mirror_lines(lines: &[u64], smudges: u32) -> impl Iterator<Item = usize> + '_ {
+    (1..lines.len()).filter(move |before| {
+        lines[..*before]
+            .iter()
+            .rev()
+            .zip(&lines[*before..])
+            .map(|(a, b)| (a ^ b).count_ones())
+            .sum::<u32>()
+            == smudges
+    })
+}
+
+/// Every pattern in @input, separated by empty lines
+pub fn parse(input: &str) -> ParseResult<Vec<Pattern>> {
+    let mut patterns = Vec::new();
+    let mut set: Vec<&str> = Vec::new();
+
+    for (row, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            if !set.is_empty() {
+                patterns.push(Pattern::new(row - set.len() + 1, &set));
+            }
+
+            set = Vec::new();
+            continue;
+        }
+
+        if let Some(bad) = line.matches(|c| c != '.' && c != '#').next() {
+            return Err(ParseError::new(row, line, bad, "\".\" or \"#\""));
+        } else if line.len() > MAX_SIZE {
+            return Err(ParseError::new(
+                row,
+                line,
+                &line[MAX_SIZE..],
+                format!("at most {} characters", MAX_SIZE),
+            ));
+        } else if set.len() == MAX_SIZE {
+            return Err(ParseError::new(
+                row,
+                line,
+                line,
+                format!("an empty line after at most {} rows", MAX_SIZE),
+            ));
+        } else if let Some(width) = set.first().map(|r| r.len()) {
+            if line.len() < width {
+                return Err(ParseError::missing(
+                    row,
+                    line,
+                    format!("{} characters", width),
+                ));
+            } else if line.len() > width {
+                return Err(ParseError::new(row, line, &line[width..], "end of line"));
+            }
+        }
+
+        set.push(line);
+    }
+
+    if !set.is_empty() {
+        patterns.push(Pattern::new(input.lines().count() - set.len() + 1, &set));
+    }
+
+    Ok(patterns)
+}
+
+/// Sum of the scores of the first line of reflection with exactly @smudges differences
+/// in every pattern, an error at the start of the first pattern that has none
+pub fn summarize(input: &str, smudges: u32) -> ParseResult<u64> {
+    let mut sum = 0;
+
+    for p in parse(input)? {
+        let Some(r) = p.reflections(smudges).first().copied() else {
+            let line = input.lines().nth(p.line - 1).unwrap();
+
+            return Err(ParseError::new(
+                p.line - 1,
+                line,
+                line,
+                format!(
+                    "a pattern with a line of reflection off by {} cell{}",
+                    smudges,
+                    if smudges == 1 { "" } else { "s" }
+                ),
+            ));
+        };
+
+        sum += r.score();
+    }
+
+    Ok(sum)
+}
+
+pub fn part1(input: &str) -> ParseResult<u64> {
+    summarize(input, 0)
+}
+
+pub fn part2(input: &str) -> ParseResult<u64> {
+    summarize(input, 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day13/example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE), Ok(405));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), Ok(400));
+    }
+
+    #[test]
+    fn no_reflection() {
+        let e = part1("##\n..\n\n#..\n..#\n.#.\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.token.as_str()), (4, 1, "#.."));
+
+        // a mirror between the columns, but none off by exactly one cell
+        let e = part2("##\n..\n").unwrap_err();
+        assert_eq!(e.line, 1);
+        assert_eq!(
+            e.expected,
+            "a pattern with a line of reflection off by 1 cell"
+        );
+    }
+
+    #[test]
+    fn every_reflection() {
+        let patterns = parse(EXAMPLE).unwrap();
+        let vertical = |before| Reflection {
+            axis: Axis::Vertical,
+            before,
+        };
+        let horizontal = |before| Reflection {
+            axis: Axis::Horizontal,
+            before,
+        };
+
+        assert_eq!(patterns[0].line, 1);
+        assert_eq!(patterns[1].line, 9);
+        assert_eq!(patterns[0].reflections(0), [vertical(5)]);
+        assert_eq!(patterns[1].reflections(1), [horizontal(1)]);
+
+        // a pattern that reflects both ways, and a third way with two smudges
+        let square = Pattern::new(1, &["#..#", "....", "....", "#..#"]);
+        assert_eq!(square.reflections(0), [horizontal(2), vertical(2)]);
+        assert_eq!(
+            square.reflections(2),
+            [horizontal(1), horizontal(3), vertical(1), vertical(3)]
+        );
+    }
+}
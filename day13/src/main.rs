@@ -1,113 +1,65 @@
 use clap::{arg, command, ArgAction};
+use common::cli::{self, Part};
+use day13::{parse, part1, part2, summarize};
 use std::io;
 
 fn main() -> Result<(), io::Error> {
-    let input = include_str!("../../input.txt");
-    let example = include_str!("../../example.txt");
-
     let matches = command!()
-        .arg(arg!(example: -e).action(ArgAction::SetTrue))
+        .args(cli::input_args())
+        .arg(cli::part_arg())
+        .arg(
+            arg!(smudges: --smudges <K> "Run once with mirrors that are off by exactly K cells (0 is part 1, 1 part 2)")
+                .value_parser(clap::value_parser!(u32)),
+        )
+        .arg(
+            arg!(lines: --lines "List every line of reflection of every pattern instead of the summary")
+                .action(ArgAction::SetTrue),
+        )
         .get_matches();
 
-    if matches.get_flag("example") {
-        println!("solution (example): {}", solution(example));
-    } else {
-        println!("solution: {}", solution(input));
-    }
-
-    Ok(())
-}
-
-fn find_horizontal_solution(rows: &Vec<String>) -> u64 {
-    let mut reflection_points = Vec::new();
-
-    for i in 0..rows.len() {
-        let mut found = true;
-        for (i, j) in ((i + 1)..rows.len()).zip((0..=i).rev()) {
-            if rows[i] != rows[j] {
-                found = false;
-                break;
-            }
+    let input = cli::read_input(13, &matches)?;
+    let part = *matches.get_one::<Part>("part").unwrap();
+    let smudges = matches.get_one::<u32>("smudges").copied();
+
+    if !matches.get_flag("lines") {
+        match smudges {
+            Some(smudges) => match summarize(&input.text, smudges) {
+                Ok(sum) => println!("summary: {}", sum),
+                Err(e) => cli::print_answer::<u64>(1, &input, Err(e)),
+            },
+            None => cli::print_parts(part, &input, part1, part2),
         }
 
-        if found {
-            reflection_points.push(i as u64);
-        }
+        return Ok(());
     }
 
-    for i in 0..rows.len() {
-        let mut smudge_found = false;
-        let mut found = true;
-
-        for (j, k) in ((i + 1)..rows.len()).zip((0..=i).rev()) {
-            let diff: Vec<u64> = rows[j]
-                .char_indices()
-                .zip(rows[k].char_indices())
-                .filter(|((_, cj), (_, ck))| cj != ck)
-                .map(|((rj, _), (_, _))| rj as u64)
-                .collect();
-
-            match diff.len() {
-                1 => {
-                    if !smudge_found {
-                        smudge_found = true;
-                    } else {
-                        found = false;
-                        break;
-                    }
-                }
-                0 => continue,
-                _ => {
-                    found = false;
-                    break;
-                }
-            }
+    let smudges = smudges.unwrap_or(if part.runs_part1() { 0 } else { 1 });
+    let patterns = match parse(&input.text) {
+        Ok(patterns) => patterns,
+        Err(e) => {
+            cli::print_answer::<u64>(1, &input, Err(e));
+            return Ok(());
         }
-
-        if found && !reflection_points.contains(&(i as u64)) {
-            return i as u64 + 1;
-        }
-    }
-
-    0
-}
-
-fn find_solution(rows: &Vec<String>) -> u64 {
-    let horizontal = find_horizontal_solution(rows) * 100;
-    if horizontal != 0 {
-        return horizontal;
-    }
-
-    let mut columns = Vec::new();
-
-    for i in 0..rows[0].len() {
-        columns.push(String::from(""));
-
-        for r in rows {
-            let c = r.chars().nth(i);
-            if let Some(c) = c {
-                columns[i] += &c.to_string();
+    };
+
+    for (i, pattern) in patterns.iter().enumerate() {
+        let lines: Vec<String> = pattern
+            .reflections(smudges)
+            .iter()
+            .map(|r| r.to_string())
+            .collect();
+
+        println!(
+            "pattern {} (line {}): {}",
+            i + 1,
+            pattern.line,
+            if lines.is_empty() {
+                "none".to_string()
+            } else {
+                lines.join(", ")
             }
-        }
+        );
     }
 
-    find_horizontal_solution(&columns)
-}
-
-fn solution(input: &str) -> u64 {
-    let lines: Vec<_> = input.lines().collect();
-    let mut sum = 0;
-
-    let mut set: Vec<String> = Vec::new();
-    for line in lines {
-        if line.is_empty() {
-            sum += find_solution(&set);
-            set = Vec::new();
-        } else {
-            set.push(line.to_string());
-        }
-    }
-
-    sum += find_solution(&set);
-    sum
+    Ok(())
 }
@@ -0,0 +1,147 @@
+use common::error::parse_num;
+use common::{cycle, ParseError, ParseResult};
+use grid::Direction;
+use platform::Platform;
+use std::fmt::Display;
+
+pub mod platform;
+
+/// Spin cycles in part 2
+pub const NUM_CYCLES: usize = 1_000_000_000;
+
+/// Directions to tilt the platform in, like "N" or "NWSE*1000000000": a round of
+/// tilts, optionally repeated a number of times
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Script {
+    pub tilts: Vec<Direction>,
+    pub repeats: usize,
+}
+
+impl Script {
+    /// Part 1: tilt north once
+    pub fn north() -> Self {
+        Script {
+            tilts: vec![Direction::Up],
+            repeats: 1,
+        }
+    }
+
+    /// Part 2: spin cycle after spin cycle
+    pub fn spin_cycles() -> Self {
+        Script {
+            tilts: vec![
+                Direction::Up,
+                Direction::Left,
+                Direction::Down,
+                Direction::Right,
+            ],
+            repeats: NUM_CYCLES,
+        }
+    }
+
+    pub fn parse(script: &str) -> ParseResult<Self> {
+        let (tilts, repeats) = match script.split_once('*') {
+            Some((tilts, repeats)) => (tilts, parse_num(0, script, repeats)?),
+            None => (script, 1),
+        };
+
+        if tilts.is_empty() {
+            return Err(ParseError::new(0, script, tilts, "a direction to tilt in"));
+        }
+
+        let tilts = tilts
+            .char_indices()
+            .map(|(i, c)| Direction::parse(0, script, &tilts[i..i + c.len_utf8()]))
+            .collect::<ParseResult<_>>()?;
+
+        Ok(Script { tilts, repeats })
+    }
+
+    /// Runs the script on @platform. The platform soon starts repeating, so this only
+    /// tilts it until it does and skips the whole rounds of the cycle after that.
+    pub fn run(&self, platform: Platform) -> Platform {
+        let round = |p: &Platform| {
+            let mut p = p.clone();
+
+            for dir in &self.tilts {
+                p.tilt(*dir);
+            }
+
+            p
+        };
+
+        cycle::nth(platform, round, self.repeats)
+    }
+}
+
+impl Display for Script {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for dir in &self.tilts {
+            let c = match dir {
+                Direction::Up => 'N',
+                Direction::Down => 'S',
+                Direction::Left => 'W',
+                Direction::Right => 'E',
+            };
+
+            write!(f, "{}", c)?;
+        }
+
+        write!(f, "*{}", self.repeats)
+    }
+}
+
+/// Load on the beams along @edge after running @script on the platform in @input
+pub fn load_after(input: &str, script: &Script, edge: Direction) -> ParseResult<u64> {
+    Ok(script.run(Platform::parse(input)?).load(edge))
+}
+
+pub fn part1(input: &str) -> ParseResult<u64> {
+    load_after(input, &Script::north(), Direction::Up)
+}
+
+pub fn part2(input: &str) -> ParseResult<u64> {
+    load_after(input, &Script::spin_cycles(), Direction::Up)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day14/example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE), Ok(136));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), Ok(64));
+    }
+
+    #[test]
+    fn few_cycles() {
+        // loads after 1 to 3 cycles, and after north alone
+        let cycles = |n| {
+            load_after(
+                EXAMPLE,
+                &Script::parse(&format!("NWSE*{}", n)).unwrap(),
+                Direction::Up,
+            )
+        };
+
+        assert_eq!(cycles(1), Ok(87));
+        assert_eq!(cycles(2), Ok(69));
+        assert_eq!(cycles(3), Ok(69));
+        assert_eq!(Script::parse("N"), Ok(Script::north()));
+        assert_eq!(Script::spin_cycles().to_string(), "NWSE*1000000000");
+    }
+
+    #[test]
+    fn bad_scripts() {
+        assert_eq!(Script::parse("NX").unwrap_err().column, 2);
+        assert_eq!(Script::parse("NW*x").unwrap_err().column, 4);
+        assert_eq!(Script::parse("*3").unwrap_err().column, 1);
+    }
+}
@@ -0,0 +1,135 @@
+use common::ParseResult;
+use grid::{Direction, Grid, Pos};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Rock {
+    Empty,
+    /// 'O', rolls when the platform is tilted
+    Round,
+    /// '#', stays in place
+    Cube,
+}
+
+impl Display for Rock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Rock::Empty => '.',
+            Rock::Round => 'O',
+            Rock::Cube => '#',
+        };
+
+        write!(f, "{}", c)
+    }
+}
+
+/// The platform of rocks, north up
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Platform {
+    rocks: Grid<Rock>,
+}
+
+impl Platform {
+    pub fn parse(input: &str) -> ParseResult<Self> {
+        let rocks = Grid::parse(input, "\"O\", \"#\" or \".\"", |c| match c {
+            '#' => Some(Rock::Cube),
+            'O' => Some(Rock::Round),
+            '.' => Some(Rock::Empty),
+            _ => None,
+        })?;
+
+        Ok(Platform { rocks })
+    }
+
+    /// The cell on the edge @dir points at of every row (or column) the rocks roll
+    /// along when tilting towards @dir
+    fn edge(&self, dir: Direction) -> Vec<Pos> {
+        let (width, height) = (self.rocks.width(), self.rocks.height());
+
+        match dir {
+            Direction::Up => (0..width).map(|c| (0, c)).collect(),
+            Direction::Down => (0..width).map(|c| (height - 1, c)).collect(),
+            Direction::Left => (0..height).map(|r| (r, 0)).collect(),
+            Direction::Right => (0..height).map(|r| (r, width - 1)).collect(),
+        }
+    }
+
+    /// Tilts the platform towards @dir, so every round rock rolls that way until it
+    /// hits the edge or another rock
+    pub fn tilt(&mut self, dir: Direction) {
+        if self.rocks.is_empty() {
+            return;
+        }
+
+        let back = dir.reverse();
+
+        for start in self.edge(dir) {
+            // where the next round rock will stop
+            let mut next = Some(start);
+            let mut pos = Some(start);
+
+            while let Some(p) = pos {
+                match self.rocks[p] {
+                    Rock::Cube => next = self.rocks.step(p, back),
+                    Rock::Round => {
+                        // a round rock can't be between the rocks before it and the edge
+                        let stop = next.unwrap();
+
+                        self.rocks[p] = Rock::Empty;
+                        self.rocks[stop] = Rock::Round;
+                        next = self.rocks.step(stop, back);
+                    }
+                    Rock::Empty => (),
+                }
+
+                pos = self.rocks.step(p, back);
+            }
+        }
+    }
+
+    /// Load on the support beams along @edge of the platform: every round rock adds
+    /// how many rows (or columns) away from the opposite edge it is
+    pub fn load(&self, edge: Direction) -> u64 {
+        let (width, height) = (self.rocks.width(), self.rocks.height());
+
+        self.rocks
+            .iter()
+            .filter(|(_, rock)| **rock == Rock::Round)
+            .map(|((r, c), _)| match edge {
+                Direction::Up => height - r,
+                Direction::Down => r + 1,
+                Direction::Left => width - c,
+                Direction::Right => c + 1,
+            } as u64)
+            .sum()
+    }
+}
+
+impl Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.rocks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tilts() {
+        let mut platform = Platform::parse("O.#O.\n..O..\n.O..O\n").unwrap();
+
+        platform.tilt(Direction::Right);
+        assert_eq!(platform.to_string(), ".O#.O\n....O\n...OO\n");
+        assert_eq!(platform.load(Direction::Right), 5 + 5 + 4 + 5 + 2);
+
+        platform.tilt(Direction::Down);
+        assert_eq!(platform.to_string(), "..#.O\n....O\n.O.OO\n");
+        assert_eq!(platform.load(Direction::Up), 3 + 2 + 1 + 1 + 1);
+
+        platform.tilt(Direction::Left);
+        platform.tilt(Direction::Up);
+        assert_eq!(platform.to_string(), "OO#O.\nO.O..\n.....\n");
+        assert_eq!(platform.load(Direction::Left), 5 + 4 + 2 + 5 + 3);
+    }
+}
@@ -0,0 +1,207 @@
+use common::error::parse_num;
+use common::{ParseError, ParseResult};
+use ordered_map::OrderedMap;
+use std::fmt::Display;
+
+pub mod ordered_map;
+
+/// The HASH algorithm: for each character add its ASCII code, multiply by 17 and keep
+/// the remainder of dividing by 256, which is what u8 arithmetic does anyway
+pub fn hash(s: &str) -> u8 {
+    s.bytes()
+        .fold(0u8, |h, b| h.wrapping_add(b).wrapping_mul(17))
+}
+
+/// A step of the initialization sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op<'a> {
+    /// "label=n": put a lens with focal length n in the label's box
+    Set(&'a str, u64),
+    /// "label-": take the lens with the label out of its box
+    Remove(&'a str),
+}
+
+impl<'a> Op<'a> {
+    /// Parses @step, a slice of @line, which is the @row'th line of the input
+    pub fn parse(row: usize, line: &'a str, step: &'a str) -> ParseResult<Self> {
+        let end = step.find(['-', '=']).unwrap_or(step.len());
+        let (label, op) = step.split_at(end);
+
+        if let Some(bad) = label.matches(|c: char| !c.is_ascii_alphanumeric()).next() {
+            return Err(ParseError::new(row, line, bad, "a letter or digit"));
+        } else if label.is_empty() {
+            return Err(ParseError::new(row, line, step, "a label"));
+        }
+
+        if op == "-" {
+            Ok(Op::Remove(label))
+        } else if let Some(n) = op.strip_prefix('=') {
+            Ok(Op::Set(label, parse_num(row, line, n)?))
+        } else {
+            Err(ParseError::new(
+                row,
+                line,
+                op,
+                "\"-\" or \"=<focal length>\"",
+            ))
+        }
+    }
+
+    pub fn label(&self) -> &'a str {
+        match self {
+            Op::Set(label, _) | Op::Remove(label) => label,
+        }
+    }
+}
+
+impl Display for Op<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Op::Set(label, n) => write!(f, "{}={}", label, n),
+            Op::Remove(label) => write!(f, "{}-", label),
+        }
+    }
+}
+
+/// Every step of the comma separated sequence in @input. Line breaks are ignored.
+pub fn parse(input: &str) -> ParseResult<Vec<Op<'_>>> {
+    let mut ops = Vec::new();
+
+    for (row, line) in input.lines().enumerate() {
+        let line = line.trim_end();
+
+        for step in line.split(',').filter(|s| !s.is_empty()) {
+            ops.push(Op::parse(row, line, step)?);
+        }
+    }
+
+    Ok(ops)
+}
+
+/// The 256 boxes of lenses, each keeping its lenses in the order they were put in
+#[derive(Debug, Clone)]
+pub struct LensLibrary<'a> {
+    boxes: Vec<OrderedMap<&'a str, u64>>,
+}
+
+impl<'a> LensLibrary<'a> {
+    pub fn new() -> Self {
+        LensLibrary {
+            boxes: (0..256).map(|_| OrderedMap::new()).collect(),
+        }
+    }
+
+    pub fn apply(&mut self, op: Op<'a>) {
+        let b = &mut self.boxes[hash(op.label()) as usize];
+
+        match op {
+            Op::Set(label, n) => {
+                b.insert(label, n);
+            }
+            Op::Remove(label) => {
+                b.remove(&label);
+            }
+        }
+    }
+
+    /// Sum of every lens's box number (1 based) times its slot (1 based) times its
+    /// focal length
+    pub fn focusing_power(&self) -> u64 {
+        self.boxes
+            .iter()
+            .enumerate()
+            .flat_map(|(b, lenses)| {
+                lenses
+                    .iter()
+                    .enumerate()
+                    .map(move |(slot, (_, n))| (b as u64 + 1) * (slot as u64 + 1) * n)
+            })
+            .sum()
+    }
+}
+
+impl Default for LensLibrary<'_> {
+    fn default() -> Self {
+        LensLibrary::new()
+    }
+}
+
+/// The boxes with lenses in them, like the puzzle shows them: "Box 0: [rn 1] [cm 2]"
+impl Display for LensLibrary<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, lenses) in self.boxes.iter().enumerate() {
+            if lenses.is_empty() {
+                continue;
+            }
+
+            write!(f, "Box {}:", i)?;
+
+            for (label, n) in lenses.iter() {
+                write!(f, " [{} {}]", label, n)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn part1(input: &str) -> ParseResult<u64> {
+    Ok(input
+        .lines()
+        .flat_map(|l| l.trim_end().split(','))
+        .filter(|s| !s.is_empty())
+        .map(|s| hash(s) as u64)
+        .sum())
+}
+
+pub fn part2(input: &str) -> ParseResult<u64> {
+    let mut library = LensLibrary::new();
+
+    for op in parse(input)? {
+        library.apply(op);
+    }
+
+    Ok(library.focusing_power())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day15/example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE), Ok(1320));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), Ok(145));
+    }
+
+    #[test]
+    fn steps() {
+        assert_eq!(hash("HASH"), 52);
+
+        let ops = parse("rn=1,cm-,a1b2=3\n").unwrap();
+        assert_eq!(
+            ops,
+            [Op::Set("rn", 1), Op::Remove("cm"), Op::Set("a1b2", 3)]
+        );
+
+        let mut library = LensLibrary::new();
+        for op in parse(EXAMPLE).unwrap() {
+            library.apply(op);
+        }
+        assert_eq!(
+            library.to_string(),
+            "Box 0: [rn 1] [cm 2]\nBox 3: [ot 7] [ab 5] [pc 6]\n"
+        );
+
+        assert_eq!(parse("rn=1,c_m-").unwrap_err().column, 7);
+        assert_eq!(parse("rn=1,cm").unwrap_err().column, 8);
+    }
+}
@@ -1,82 +1,38 @@
 use clap::{arg, command, ArgAction};
-use std::collections::HashMap;
+use common::cli::{self, Part};
+use day15::{parse, part1, part2, LensLibrary};
 use std::io;
 
 fn main() -> Result<(), io::Error> {
-    let input = include_str!("../../input.txt");
-    let example = include_str!("../../example.txt");
-
     let matches = command!()
-        .arg(arg!(example: -e).action(ArgAction::SetTrue))
+        .args(cli::input_args())
+        .arg(cli::part_arg())
+        .arg(
+            arg!(trace: --trace "Print the boxes after every step of part 2, like the puzzle does")
+                .action(ArgAction::SetTrue),
+        )
         .get_matches();
 
-    if matches.get_flag("example") {
-        println!("solution (example): {}", solution(example));
-    } else {
-        println!("solution: {}", solution(input));
-    }
-
-    Ok(())
-}
-
-fn hash(input: &str) -> u64 {
-    input.chars().fold(0, |out, c| (out + c as u64) * 17 % 256)
-}
-
-fn insert_lens(lens: &str, boxes: &mut HashMap<u64, Vec<(String, u64)>>) {
-    let label: String = lens.chars().filter(|c| c.is_alphabetic()).collect();
-    let op: String = lens.chars().filter(|c| !c.is_alphabetic()).collect();
+    let input = cli::read_input(15, &matches)?;
+    let part = *matches.get_one::<Part>("part").unwrap();
 
-    if lens.contains('-') {
-        if let Some(b) = boxes.get_mut(&hash(&label)) {
-            for idx in 0..b.len() {
-                if b[idx].0 == label {
-                    b.remove(idx);
-                    break;
-                }
+    if matches.get_flag("trace") && part.runs_part2() {
+        let ops = match parse(&input.text) {
+            Ok(ops) => ops,
+            Err(e) => {
+                cli::print_answer::<u64>(2, &input, Err(e));
+                return Ok(());
             }
-        }
-    } else {
-        let n: u64 = op
-            .chars()
-            .skip(1)
-            .collect::<String>()
-            .parse::<u64>()
-            .unwrap();
-
-        boxes
-            .entry(hash(&label))
-            .and_modify(|b| {
-                let mut contains = false;
-                for l in b.iter_mut() {
-                    if l.0 == label {
-                        *l = (label.clone(), n);
-                        contains = true;
-                        break;
-                    }
-                }
+        };
+        let mut library = LensLibrary::new();
 
-                if !contains {
-                    b.push((label.clone(), n));
-                }
-            })
-            .or_insert(vec![(label, n)]);
+        for op in ops {
+            library.apply(op);
+            println!("After \"{}\":\n{}", op, library);
+        }
     }
-}
 
-fn solution(input: &str) -> u64 {
-    let sequence: Vec<_> = input.trim().split(',').collect();
+    cli::print_parts(part, &input, part1, part2);
 
-    let mut boxes = HashMap::new();
-    for s in sequence {
-        insert_lens(s, &mut boxes);
-    }
-
-    boxes.iter().fold(0, |pow, (b, v)| {
-        pow + v
-            .iter()
-            .enumerate()
-            .map(|(i, l)| (1 + b) * (i as u64 + 1) * l.1)
-            .sum::<u64>()
-    })
+    Ok(())
 }
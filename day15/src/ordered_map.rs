@@ -0,0 +1,109 @@
+//! A map that remembers the order keys were inserted in.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A map with O(1) lookups, inserts and removals that iterates in insertion order.
+/// Entries are kept in a `Vec` in order, with a `HashMap` from each key to its slot.
+/// Removed entries leave a gap that is cleared out once gaps fill half the slots.
+#[derive(Debug, Clone)]
+pub struct OrderedMap<K, V> {
+    slots: Vec<Option<(K, V)>>,
+    index: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq + Clone, V> OrderedMap<K, V> {
+    pub fn new() -> Self {
+        OrderedMap {
+            slots: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.slots[*self.index.get(key)?].as_ref().map(|(_, v)| v)
+    }
+
+    /// Sets @key to @value, keeping its place if it was already there and adding it
+    /// at the end if it wasn't. Returns the old value.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(i) = self.index.get(&key) {
+            let (_, old) = self.slots[*i].as_mut().unwrap();
+
+            return Some(std::mem::replace(old, value));
+        }
+
+        self.index.insert(key.clone(), self.slots.len());
+        self.slots.push(Some((key, value)));
+
+        None
+    }
+
+    /// Removes @key, leaving the order of the rest as it was
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let i = self.index.remove(key)?;
+        let (_, value) = self.slots[i].take().unwrap();
+
+        if self.index.len() * 2 < self.slots.len() {
+            self.compact();
+        }
+
+        Some(value)
+    }
+
+    /// Clears out the gaps left by removed entries
+    fn compact(&mut self) {
+        self.slots.retain(|s| s.is_some());
+
+        for (i, (key, _)) in self.slots.iter().flatten().enumerate() {
+            *self.index.get_mut(key).unwrap() = i;
+        }
+    }
+
+    /// Entries in the order their keys were first inserted
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.slots.iter().flatten().map(|(k, v)| (k, v))
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> Default for OrderedMap<K, V> {
+    fn default() -> Self {
+        OrderedMap::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_order() {
+        let mut map = OrderedMap::new();
+
+        for (k, v) in [("a", 1), ("b", 2), ("c", 3), ("d", 4)] {
+            map.insert(k, v);
+        }
+
+        assert_eq!(map.insert("b", 5), Some(2));
+        assert_eq!(map.remove(&"a"), Some(1));
+        assert_eq!(map.remove(&"c"), Some(3));
+        assert_eq!(map.remove(&"c"), None);
+        // the gaps have been cleared out by now
+        assert_eq!(map.remove(&"d"), Some(4));
+        map.insert("a", 6);
+        map.insert("e", 7);
+
+        let entries: Vec<_> = map.iter().map(|(k, v)| (*k, *v)).collect();
+        assert_eq!(entries, [("b", 5), ("a", 6), ("e", 7)]);
+        assert_eq!(map.get(&"e"), Some(&7));
+        assert_eq!(map.len(), 3);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{Contraption, Entry};
+use grid::{Direction, Grid, Pos};
+
+/// Bit for @dir in the visited directions of a tile
+pub(crate) fn bit(dir: Direction) -> u8 {
+    1 << Direction::ALL.iter().position(|d| *d == dir).unwrap()
+}
+
+/// A light beam on a tile, about to go through it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LightBeam {
+    /// (row, column)
+    pub pos: Pos,
+    pub direction: Direction,
+}
+
+impl LightBeam {
+    pub fn new(pos: Pos, direction: Direction) -> Self {
+        LightBeam { pos, direction }
+    }
+
+    /// Takes the beam through its tile and on to the next one. Returns the beam and
+    /// the beam split off (if applicable), without those that left the contraption.
+    /// Beams that come to a tile going a way a beam already went there are dropped,
+    /// as they would only light up the same tiles again; @visited keeps the ways the
+    /// beams went through every tile.
+    pub fn update(self, contraption: &Contraption, visited: &mut Grid<u8>) -> [Option<Self>; 2] {
+        let seen = &mut visited[self.pos];
+
+        if *seen & bit(self.direction) != 0 {
+            return [None, None];
+        }
+
+        *seen |= bit(self.direction);
+
+        let tiles = &contraption.tiles;
+        let (dir, split) = tiles[self.pos].deflect(self.direction);
+        let moved = |dir| tiles.step(self.pos, dir).map(|p| LightBeam::new(p, dir));
+
+        [moved(dir), split.and_then(moved)]
+    }
+}
+
+/// The beams coming from one entry, moved through the contraption a tile at a time
+pub struct Beams<'a> {
+    pub(crate) contraption: &'a Contraption,
+    beams: Vec<LightBeam>,
+    /// bits of the directions beams went through every tile in
+    pub(crate) visited: Grid<u8>,
+}
+
+impl<'a> Beams<'a> {
+    pub fn new(contraption: &'a Contraption, (pos, dir): Entry) -> Self {
+        let tiles = &contraption.tiles;
+
+        Beams {
+            contraption,
+            beams: vec![LightBeam::new(pos, dir)],
+            visited: Grid::new(tiles.width(), tiles.height(), 0),
+        }
+    }
+
+    /// Moves every beam on by a tile. Returns whether there are any beams left.
+    pub fn step(&mut self) -> bool {
+        let beams = std::mem::take(&mut self.beams);
+
+        for beam in beams {
+            let next = beam.update(self.contraption, &mut self.visited);
+
+            self.beams.extend(next.into_iter().flatten());
+        }
+
+        !self.beams.is_empty()
+    }
+
+    /// The beams still moving, on the tiles they are about to go through
+    pub fn beams(&self) -> &[LightBeam] {
+        &self.beams
+    }
+
+    pub fn is_energised(&self, pos: Pos) -> bool {
+        self.visited[pos] != 0
+    }
+
+    pub fn energised_count(&self) -> usize {
+        self.visited.cells().filter(|v| **v != 0).count()
+    }
+}
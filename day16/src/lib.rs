@@ -0,0 +1,207 @@
+use beam::Beams;
+use common::{ParseError, ParseResult};
+use grid::{Direction, Grid, Pos};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+pub mod beam;
+pub mod memo;
+pub mod render;
+
+/// Where a beam comes in from the edge: the tile it enters and the way it's going
+pub type Entry = (Pos, Direction);
+
+#[derive(Debug, Clone, Copy)]
+struct Tile {
+    obstacle: Option<char>,
+}
+
+impl Tile {
+    /// Returns None if @ch is not an empty space or an obstacle
+    fn new(ch: char) -> Option<Self> {
+        match ch {
+            '.' => Some(Tile { obstacle: None }),
+            '|' | '/' | '\\' | '-' => Some(Tile { obstacle: Some(ch) }),
+            _ => None,
+        }
+    }
+
+    /// Takes the direction @dir of a beam and returns the new direction after passing
+    /// through this Tile, and the direction of the beam split off (if applicable)
+    fn deflect(&self, dir: Direction) -> (Direction, Option<Direction>) {
+        match self.obstacle {
+            None => (dir, None),
+            Some('/') if dir.is_horizontal() => (dir.turn_left(), None),
+            Some('/') => (dir.turn_right(), None),
+            Some('\\') if dir.is_horizontal() => (dir.turn_right(), None),
+            Some('\\') => (dir.turn_left(), None),
+            Some('|') if dir.is_horizontal() => (dir.turn_left(), Some(dir.turn_right())),
+            Some('-') if !dir.is_horizontal() => (dir.turn_right(), Some(dir.turn_left())),
+            _ => (dir, None),
+        }
+    }
+}
+
+/// The grid of mirrors and splitters
+#[derive(Debug, Clone)]
+pub struct Contraption {
+    tiles: Grid<Tile>,
+}
+
+impl Contraption {
+    pub fn parse(input: &str) -> ParseResult<Self> {
+        let tiles = Grid::parse(input, "a tile", Tile::new)?;
+
+        if tiles.is_empty() {
+            return Err(ParseError::missing(0, "", "a tile"));
+        }
+
+        Ok(Contraption { tiles })
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.tiles.contains(pos)
+    }
+
+    /// Every tile on the edge a beam can come in from, going away from that edge
+    pub fn entries(&self) -> Vec<Entry> {
+        let (width, height) = (self.tiles.width(), self.tiles.height());
+
+        (0..height)
+            .flat_map(|r| {
+                [
+                    ((r, 0), Direction::Right),
+                    ((r, width - 1), Direction::Left),
+                ]
+            })
+            .chain(
+                (0..width)
+                    .flat_map(|c| [((0, c), Direction::Down), ((height - 1, c), Direction::Up)]),
+            )
+            .collect()
+    }
+
+    /// Number of tiles energised by a beam coming in at @entry
+    pub fn energised_count(&self, entry: Entry) -> usize {
+        let mut beams = Beams::new(self, entry);
+        while beams.step() {}
+
+        beams.energised_count()
+    }
+
+    /// The entry that energises the most tiles and how many it does, trying the
+    /// entries on @threads threads. Ties go to the first in `entries`.
+    pub fn best_entry(&self, threads: usize) -> (Entry, usize) {
+        let entries = self.entries();
+        let next = AtomicUsize::new(0);
+
+        let best = thread::scope(|s| {
+            let workers: Vec<_> = (0..threads.max(1))
+                .map(|_| {
+                    s.spawn(|| {
+                        let mut best: Option<(usize, usize)> = None;
+
+                        // each worker takes the next entry nobody has tried yet
+                        loop {
+                            let i = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(entry) = entries.get(i) else {
+                                return best;
+                            };
+                            let count = self.energised_count(*entry);
+
+                            if best.is_none_or(|(_, most)| count > most) {
+                                best = Some((i, count));
+                            }
+                        }
+                    })
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .filter_map(|w| w.join().unwrap())
+                .max_by_key(|(i, count)| (*count, std::cmp::Reverse(*i)))
+        });
+
+        let (i, count) = best.unwrap();
+        (entries[i], count)
+    }
+}
+
+/// Number of threads to try the entries on, one per core
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+pub fn part1(input: &str) -> ParseResult<u64> {
+    let contraption = Contraption::parse(input)?;
+
+    Ok(contraption.energised_count(((0, 0), Direction::Right)) as u64)
+}
+
+pub fn part2(input: &str) -> ParseResult<u64> {
+    let contraption = Contraption::parse(input)?;
+
+    Ok(contraption.best_entry(default_threads()).1 as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use memo::Memo;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day16/example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE), Ok(46));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), Ok(51));
+    }
+
+    #[test]
+    fn best_entry() {
+        let contraption = Contraption::parse(EXAMPLE).unwrap();
+
+        assert_eq!(contraption.best_entry(1), (((0, 3), Direction::Down), 51));
+        assert_eq!(contraption.best_entry(3), (((0, 3), Direction::Down), 51));
+        assert_eq!(
+            Memo::new(&contraption).best_entry(),
+            (((0, 3), Direction::Down), 51)
+        );
+    }
+
+    #[test]
+    fn memo_matches_beams() {
+        // pseudo random contraptions, with loops through splitters the old tracer
+        // never got out of
+        let mut seed: u64 = 16;
+
+        for size in [5, 12, 30] {
+            let input: String = (0..size)
+                .map(|_| {
+                    let row: String = (0..size)
+                        .map(|_| {
+                            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+                            ['.', '.', '.', '|', '-', '/', '\\'][(seed >> 33) as usize % 7]
+                        })
+                        .collect();
+
+                    row + "\n"
+                })
+                .collect();
+            let contraption = Contraption::parse(&input).unwrap();
+            let memo = Memo::new(&contraption);
+
+            for entry in contraption.entries() {
+                assert_eq!(
+                    memo.energised_count(entry),
+                    contraption.energised_count(entry)
+                );
+            }
+        }
+    }
+}
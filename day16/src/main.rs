@@ -1,285 +1,164 @@
+use clap::error::ErrorKind;
 use clap::{arg, command, ArgAction};
-use std::io;
-use std::thread;
-
-#[derive(Clone, Copy)]
-struct Tile {
-    energized: bool,
-    direction: (i32, i32),
-    obstacle: Option<char>,
-}
-
-impl Tile {
-    fn new(ch: char) -> Self {
-        match ch {
-            '.' => Tile {
-                energized: false,
-                direction: (0, 0),
-                obstacle: None,
-            },
-            '|' | '/' | '\\' | '-' => Tile {
-                energized: false,
-                direction: (0, 0),
-                obstacle: Some(ch),
-            },
-            _ => panic!("Invalid input to Tile constructor: {}", ch),
-        }
+use common::cli::{self, Input, Part};
+use common::error::parse_num;
+use common::{ParseError, ParseResult};
+use day16::beam::Beams;
+use day16::memo::Memo;
+use day16::render::Format;
+use day16::{default_threads, part1, Contraption, Entry};
+use grid::Direction;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::{fs, process};
+
+/// Parses @entry, "ROW,COL,DIR" or "best"
+fn parse_entry(entry: &str, contraption: &Contraption) -> ParseResult<Entry> {
+    if entry == "best" {
+        return Ok(Memo::new(contraption).best_entry().0);
     }
 
-    /// Takes a LightBeam and returns the new direction after passing through this Tile.
-    /// Tile will become energized if it doesn't contain an obstacle.
-    ///
-    /// Arguments
-    /// `@beam:` LightBeam to use for calculations
-    ///
-    /// `returns:` Direction of @beam in first tuple, direction of new LightBeam in second
-    /// tuple (if applicable)
-    fn deflect(&mut self, beam: &LightBeam) -> ((i32, i32), Option<(i32, i32)>) {
-        self.energized = true;
+    let mut parts = entry.splitn(3, ',');
+    let (row, col, dir) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(row), Some(col), Some(dir)) => (row, col, dir),
+        _ => return Err(ParseError::missing(0, entry, "ROW,COL,DIR")),
+    };
 
-        if self.obstacle.is_none() {
-            self.direction = beam.direction;
-            return (beam.direction, None);
-        }
-
-        match self.obstacle.unwrap() {
-            '/' => ((-beam.direction.1, -beam.direction.0), None),
-            '\\' => ((beam.direction.1, beam.direction.0), None),
-            '|' => match beam.direction {
-                (0, 1) => ((-1, 0), Some((1, 0))),
-                (0, -1) => ((1, 0), Some((-1, 0))),
-                _ => (beam.direction, None),
-            },
-            '-' => match beam.direction {
-                (1, 0) => ((0, -1), Some((0, 1))),
-                (-1, 0) => ((0, 1), Some((0, -1))),
-                _ => (beam.direction, None),
-            },
-            _ => ((0, 0), None),
-        }
-    }
+    Ok((
+        (parse_num(0, entry, row)?, parse_num(0, entry, col)?),
+        Direction::parse(0, entry, dir)?,
+    ))
 }
 
-struct LightBeam {
-    /// direction is normalized velocity as (row, column) (or (y, x))
-    direction: (i32, i32),
-    /// (row, column)
-    pos: (usize, usize),
-    /// whether this light beam should be destroyed
-    cleanup: bool,
-}
-
-impl LightBeam {
-    fn new(pos: (usize, usize), direction: (i32, i32)) -> Self {
-        LightBeam {
-            pos,
-            direction,
-            cleanup: false,
-        }
+/// Follows the beams from @entry, writing a picture in @format of every step into
+/// @frames if given and of the end result to stdout if @render
+fn show(
+    input: &Input,
+    contraption: &Contraption,
+    entry: Entry,
+    format: Format,
+    render: bool,
+    frames: Option<&Path>,
+) -> io::Result<()> {
+    let mut beams = Beams::new(contraption, entry);
+    let mut frame = 0;
+
+    if let Some(dir) = frames {
+        fs::create_dir_all(dir)?;
     }
 
-    fn update_position(&mut self, tiles: &Vec<Vec<Tile>>) {
-        match self.direction {
-            (-1, 0) => {
-                if self.pos.0 == 0 {
-                    self.cleanup = true
-                } else {
-                    self.pos.0 -= 1;
-                }
-            }
-            (0, -1) => {
-                if self.pos.1 == 0 {
-                    self.cleanup = true
-                } else {
-                    self.pos.1 -= 1;
-                }
-            }
-            (1, 0) => {
-                if self.pos.0 == tiles.len() - 1 {
-                    self.cleanup = true
-                } else {
-                    self.pos.0 += 1;
-                }
-            }
-            (0, 1) => {
-                if self.pos.1 == tiles[0].len() - 1 {
-                    self.cleanup = true
-                } else {
-                    self.pos.1 += 1;
-                }
-            }
-            _ => panic!("Invalid direction for a LightBeam: {:?}", self.direction),
-        };
-    }
-
-    fn update(&mut self, tiles: &mut Vec<Vec<Tile>>) -> Option<LightBeam> {
-        if self.cleanup {
-            return None;
+    loop {
+        if let Some(dir) = frames {
+            let path = dir.join(format!("frame_{:04}.{}", frame, format.extension()));
+            fs::write(path, format.render(&beams))?;
         }
 
-        let tile = &mut tiles[self.pos.0][self.pos.1];
-        if tile.direction == self.direction && tile.energized {
-            self.cleanup = true;
-            return None;
-        }
-
-        let (new_dir, new_beam) = tile.deflect(self);
-        self.direction = new_dir;
-
-        self.update_position(tiles);
-
-        if new_beam.is_some() {
-            let new_beam = new_beam.unwrap();
-            let r_dir = if new_beam.0 < 0 {
-                self.pos
-                    .0
-                    .saturating_sub(new_beam.0.unsigned_abs() as usize)
-            } else {
-                self.pos.0 + new_beam.0 as usize
-            };
-            let c_dir = if new_beam.1 < 0 {
-                self.pos
-                    .1
-                    .saturating_sub(new_beam.1.unsigned_abs() as usize)
-            } else {
-                self.pos.1 + new_beam.1 as usize
-            };
-
-            Some(LightBeam::new((r_dir, c_dir), new_beam))
-        } else {
-            None
+        frame += 1;
+        if !beams.step() {
+            break;
         }
     }
-}
-
-// for debug
-#[allow(unused)]
-fn print_tiles(tiles: &Vec<Vec<Tile>>) {
-    for r in tiles {
-        for t in r {
-            match t.obstacle {
-                Some(ch) => print!("{}", ch),
-                None => {
-                    if t.energized {
-                        print!(
-                            "{}",
-                            match t.direction {
-                                (-1, 0) => '^',
-                                (1, 0) => 'V',
-                                (0, -1) => '<',
-                                (0, 1) => '>',
-                                _ => '.',
-                            }
-                        );
-                    } else {
-                        print!(".");
-                    }
-                }
-            }
-        }
 
-        println!();
+    if render {
+        io::stdout().write_all(&format.render(&beams))?;
     }
-}
-
-fn main() -> Result<(), io::Error> {
-    let input = include_str!("../../input.txt");
-    let example = include_str!("../../example.txt");
 
-    let matches = command!()
-        .arg(arg!(example: -e).action(ArgAction::SetTrue))
-        .get_matches();
-
-    if matches.get_flag("example") {
-        println!("solution (example): {}", solution(example));
-    } else {
-        println!("solution: {}", solution(input));
+    // an image on stdout is on its own
+    if !(render && format == Format::Ppm) {
+        println!("energised: {}", beams.energised_count());
     }
 
-    Ok(())
-}
-
-fn calculate_beam(beam: LightBeam, mut tiles: Vec<Vec<Tile>>) -> u64 {
-    let mut beams = Vec::new();
-    beams.push(beam);
-
-    while beams.iter().any(|b| !b.cleanup) {
-        let mut new_beams = Vec::new();
-
-        for b in beams.iter_mut() {
-            let new_beam = b.update(&mut tiles);
-
-            if let Some(nb) = new_beam {
-                new_beams.push(nb);
-            }
-        }
-
-        beams.append(&mut new_beams);
+    if let Some(dir) = frames {
+        eprintln!(
+            "{}: wrote {} frames to {}",
+            input.name,
+            frame,
+            dir.display()
+        );
     }
 
-    tiles.iter().fold(0, |acc, v| {
-        acc + v
-            .iter()
-            .map(|t| if t.energized { 1 } else { 0 })
-            .sum::<u64>()
-    })
+    Ok(())
 }
 
-fn solution(input: &str) -> u64 {
-    let lines: Vec<_> = input.lines().collect();
-    let mut tiles = Vec::new();
-    let mut handles = Vec::new();
-
-    for (i, line) in lines.iter().enumerate() {
-        tiles.push(Vec::new());
-
-        for (_, c) in line.char_indices() {
-            tiles[i].push(Tile::new(c));
-        }
-    }
-
-    for i in 0..tiles.len() {
-        let t1 = tiles.clone();
-        let t2 = tiles.clone();
-
-        let h1 = thread::spawn(move || {
-            let beam = LightBeam::new((i, 0), (0, 1));
-            calculate_beam(beam, t1)
-        });
-
-        handles.push(h1);
-
-        let h2 = thread::spawn(move || {
-            let beam = LightBeam::new((i, t2[0].len() - 1), (0, -1));
-            calculate_beam(beam, t2)
+fn main() -> Result<(), io::Error> {
+    let mut command = command!()
+        .args(cli::input_args())
+        .arg(cli::part_arg())
+        .arg(
+            arg!(threads: --threads <N> "Try the entries of part 2 on N threads (default: one per core)")
+                .value_parser(clap::value_parser!(u64).range(1..)),
+        )
+        .arg(
+            arg!(memo: --memo "Work out part 2 by sharing what the splitters light up between the entries")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("threads"),
+        )
+        .arg(arg!(entry: --entry <ENTRY> "Follow the beam coming in at ROW,COL,DIR (like 0,3,v), or the best entry with \"best\", instead of running the parts"))
+        .arg(
+            arg!(render: --render [FORMAT] "Print a picture of the tiles the beam lights up")
+                .value_parser(clap::value_parser!(Format))
+                .default_missing_value("map"),
+        )
+        .arg(
+            arg!(frames: --frames <DIR> "Write a picture of every step of the beams into DIR, as --render's FORMAT (default paths)")
+                .value_parser(clap::value_parser!(PathBuf)),
+        );
+    let matches = command.get_matches_mut();
+
+    let input = cli::read_input(16, &matches)?;
+    let part = *matches.get_one::<Part>("part").unwrap();
+    let threads = matches
+        .get_one::<u64>("threads")
+        .map_or_else(default_threads, |n| *n as usize);
+    let memo = matches.get_flag("memo");
+
+    let entry = matches.get_one::<String>("entry");
+    let render = matches.get_one::<Format>("render");
+    let frames = matches.get_one::<PathBuf>("frames");
+
+    // following a single beam replaces both parts, starting from part 1's entry
+    if entry.is_some() || render.is_some() || frames.is_some() {
+        let contraption = Contraption::parse(&input.text).unwrap_or_else(|e| {
+            eprintln!("error: {}:{}", input.name, e);
+            process::exit(1);
         });
-
-        handles.push(h2);
+        let entry = match entry.map(|e| parse_entry(e, &contraption)) {
+            Some(Ok(((r, c), _))) if !contraption.contains((r, c)) => command
+                .error(
+                    ErrorKind::ValueValidation,
+                    format!("--entry {},{} is outside the contraption", r, c),
+                )
+                .exit(),
+            Some(Ok(entry)) => entry,
+            Some(Err(e)) => command
+                .error(ErrorKind::ValueValidation, format!("--entry:{}", e))
+                .exit(),
+            None => ((0, 0), Direction::Right),
+        };
+        let format = render.copied().unwrap_or(Format::Paths);
+
+        return show(
+            &input,
+            &contraption,
+            entry,
+            format,
+            render.is_some(),
+            frames.map(PathBuf::as_path),
+        );
     }
 
-    for j in 0..tiles[0].len() {
-        let t1 = tiles.clone();
-        let t2 = tiles.clone();
-
-        let h1 = thread::spawn(move || {
-            let beam = LightBeam::new((0, j), (1, 0));
-            calculate_beam(beam, t1)
-        });
-
-        handles.push(h1);
+    let part2 = |input: &str| {
+        let contraption = Contraption::parse(input)?;
+        let (_, count) = if memo {
+            Memo::new(&contraption).best_entry()
+        } else {
+            contraption.best_entry(threads)
+        };
 
-        let h2 = thread::spawn(move || {
-            let beam = LightBeam::new((t2.len() - 1, j), (-1, 0));
-            calculate_beam(beam, t2)
-        });
+        Ok(count as u64)
+    };
 
-        handles.push(h2);
-    }
+    cli::print_parts(part, &input, part1, part2);
 
-    let mut sols = Vec::new();
-    for h in handles {
-        sols.push(h.join().unwrap());
-    }
-
-    *sols.iter().max().unwrap()
+    Ok(())
 }
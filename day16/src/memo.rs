@@ -0,0 +1,264 @@
+//! Energised tiles for every entry at once, sharing the work between entries.
+//!
+//! A beam goes one way until a splitter splits it, and a splitter always sends its
+//! two beams the same way whichever side it was hit from. So the tiles lit up by a
+//! splitter that has been hit are the same every time: its own tile, the tiles on its
+//! two beams' paths and whatever the splitters those hit light up. Splitters can light
+//! each other up in a loop, so the splitters are grouped into strongly connected
+//! components which light up the same tiles, and each component's tiles are worked out
+//! once as a bitset, after the components it leads to.
+
+use crate::{Contraption, Entry};
+use grid::{Direction, Grid, Pos};
+use std::collections::HashSet;
+
+/// A set of tiles, one bit per tile row by row
+#[derive(Debug, Clone)]
+struct TileSet {
+    words: Vec<u64>,
+    width: usize,
+}
+
+impl TileSet {
+    fn new(grid: &Grid<impl Sized>) -> Self {
+        TileSet {
+            words: vec![0; (grid.width() * grid.height()).div_ceil(64)],
+            width: grid.width(),
+        }
+    }
+
+    fn insert(&mut self, (r, c): Pos) {
+        let i = r * self.width + c;
+
+        self.words[i / 64] |= 1 << (i % 64);
+    }
+
+    fn union(&mut self, other: &TileSet) {
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a |= b;
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+}
+
+pub struct Memo<'a> {
+    contraption: &'a Contraption,
+    /// index of every splitter
+    splitters: Grid<Option<usize>>,
+    /// component of every splitter
+    component: Vec<usize>,
+    /// tiles lit up once a splitter of the component is hit
+    lit: Vec<TileSet>,
+}
+
+impl<'a> Memo<'a> {
+    pub fn new(contraption: &'a Contraption) -> Self {
+        let tiles = &contraption.tiles;
+        let mut splitters = Grid::new(tiles.width(), tiles.height(), None);
+        let mut positions = Vec::new();
+
+        for (pos, tile) in tiles.iter() {
+            if matches!(tile.obstacle, Some('|' | '-')) {
+                splitters[pos] = Some(positions.len());
+                positions.push(pos);
+            }
+        }
+
+        let mut memo = Memo {
+            contraption,
+            splitters,
+            component: Vec::new(),
+            lit: Vec::new(),
+        };
+
+        // the tiles of each splitter's own paths, and the splitters at their ends
+        let mut own = Vec::new();
+        let mut edges = Vec::new();
+
+        for pos in &positions {
+            let mut set = TileSet::new(tiles);
+            let mut hits = Vec::new();
+            set.insert(*pos);
+
+            let dir = if tiles[*pos].obstacle == Some('|') {
+                Direction::Up
+            } else {
+                Direction::Left
+            };
+
+            for out in [dir, dir.reverse()] {
+                let Some(start) = tiles.step(*pos, out) else {
+                    continue;
+                };
+                let (path, hit) = memo.trace(start, out);
+
+                path.into_iter().for_each(|p| set.insert(p));
+                hits.extend(hit);
+            }
+
+            own.push(set);
+            edges.push(hits);
+        }
+
+        let (component, count) = components(&edges);
+        let mut lit: Vec<Option<TileSet>> = vec![None; count];
+
+        // components come out of `components` after every component they lead to
+        for c in 0..count {
+            let mut set = TileSet::new(tiles);
+
+            for s in (0..positions.len()).filter(|s| component[*s] == c) {
+                set.union(&own[s]);
+
+                for next in edges[s].iter().filter(|n| component[**n] != c) {
+                    set.union(lit[component[*next]].as_ref().unwrap());
+                }
+            }
+
+            lit[c] = Some(set);
+        }
+
+        memo.component = component;
+        memo.lit = lit.into_iter().map(Option::unwrap).collect();
+        memo
+    }
+
+    /// Follows a beam coming onto @pos going @dir until it leaves the contraption, goes
+    /// round in a loop or a splitter splits it. Returns the tiles it went through and
+    /// the index of that splitter.
+    fn trace(&self, mut pos: Pos, mut dir: Direction) -> (Vec<Pos>, Option<usize>) {
+        let tiles = &self.contraption.tiles;
+        let mut seen = HashSet::new();
+        let mut path = Vec::new();
+
+        while seen.insert((pos, dir)) {
+            path.push(pos);
+
+            let (next, split) = tiles[pos].deflect(dir);
+            if split.is_some() {
+                return (path, self.splitters[pos]);
+            }
+
+            dir = next;
+            match tiles.step(pos, dir) {
+                Some(p) => pos = p,
+                None => break,
+            }
+        }
+
+        (path, None)
+    }
+
+    /// Number of tiles energised by a beam coming in at @entry
+    pub fn energised_count(&self, (pos, dir): Entry) -> usize {
+        let (path, hit) = self.trace(pos, dir);
+        let mut set = match hit {
+            Some(s) => self.lit[self.component[s]].clone(),
+            None => TileSet::new(&self.contraption.tiles),
+        };
+
+        path.into_iter().for_each(|p| set.insert(p));
+        set.len()
+    }
+
+    /// The entry that energises the most tiles and how many it does. Ties go to the
+    /// first in `Contraption::entries`.
+    pub fn best_entry(&self) -> (Entry, usize) {
+        self.contraption
+            .entries()
+            .into_iter()
+            .map(|e| (e, self.energised_count(e)))
+            .rev()
+            .max_by_key(|(_, count)| *count)
+            .unwrap()
+    }
+}
+
+/// Strongly connected components of the graph with @edges from every node, by Tarjan's
+/// algorithm without recursion. Returns the component of every node and the number of
+/// components, which are numbered so that edges only lead to the same or a lower one.
+fn components(edges: &[Vec<usize>]) -> (Vec<usize>, usize) {
+    let n = edges.len();
+    let mut index = vec![usize::MAX; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut component = vec![usize::MAX; n];
+    let mut next_index = 0;
+    let mut count = 0;
+
+    for root in 0..n {
+        if index[root] != usize::MAX {
+            continue;
+        }
+
+        // nodes being visited and how many of their edges have been followed
+        let mut work = vec![(root, 0)];
+        index[root] = next_index;
+        low[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some((v, i)) = work.last_mut() {
+            let v = *v;
+
+            if let Some(w) = edges[v].get(*i).copied() {
+                *i += 1;
+
+                if index[w] == usize::MAX {
+                    index[w] = next_index;
+                    low[w] = next_index;
+                    next_index += 1;
+                    stack.push(w);
+                    on_stack[w] = true;
+                    work.push((w, 0));
+                } else if on_stack[w] {
+                    low[v] = low[v].min(index[w]);
+                }
+
+                continue;
+            }
+
+            work.pop();
+            if let Some((u, _)) = work.last() {
+                low[*u] = low[*u].min(low[v]);
+            }
+
+            if low[v] == index[v] {
+                loop {
+                    let w = stack.pop().unwrap();
+                    on_stack[w] = false;
+                    component[w] = count;
+
+                    if w == v {
+                        break;
+                    }
+                }
+
+                count += 1;
+            }
+        }
+    }
+
+    (component, count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strongly_connected() {
+        // 0 -> 1 -> 2 -> 0 is a loop, which leads to 3, which leads to 4 and back
+        let edges = [vec![1], vec![2], vec![0, 3], vec![4], vec![3]];
+        let (component, count) = components(&edges);
+
+        assert_eq!(count, 2);
+        assert!(component[..3].iter().all(|c| *c == 1));
+        assert_eq!(component[3..], [0, 0]);
+    }
+}
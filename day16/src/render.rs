@@ -0,0 +1,140 @@
+//! Pictures of the tiles the beams from an entry have lit up so far.
+
+use crate::beam::{bit, Beams};
+use clap::builder::PossibleValue;
+use clap::ValueEnum;
+use grid::{Direction, Pos};
+
+/// Pixels along the side of a tile in images
+const SCALE: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// '#' for every energised tile and '.' for the rest, like the puzzle
+    Map,
+    /// the mirrors and splitters, the way the beams went through every empty tile
+    /// (or how many beams did if more than one) and '*' where the beams are
+    Paths,
+    /// a PPM image of the paths
+    Ppm,
+}
+
+impl Format {
+    /// Extension of a file holding a picture in this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Map | Format::Paths => "txt",
+            Format::Ppm => "ppm",
+        }
+    }
+
+    pub fn render(&self, beams: &Beams) -> Vec<u8> {
+        match self {
+            Format::Map => map(beams).into_bytes(),
+            Format::Paths => paths(beams).into_bytes(),
+            Format::Ppm => ppm(beams),
+        }
+    }
+}
+
+impl ValueEnum for Format {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Format::Map, Format::Paths, Format::Ppm]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Format::Map => PossibleValue::new("map"),
+            Format::Paths => PossibleValue::new("paths"),
+            Format::Ppm => PossibleValue::new("ppm"),
+        })
+    }
+}
+
+/// Draws every tile of @beams' contraption as the character @draw returns for it
+fn text(beams: &Beams, draw: impl Fn(Pos) -> char) -> String {
+    let tiles = &beams.contraption.tiles;
+    let mut s = String::new();
+
+    for r in 0..tiles.height() {
+        s.extend((0..tiles.width()).map(|c| draw((r, c))));
+        s.push('\n');
+    }
+
+    s
+}
+
+pub fn map(beams: &Beams) -> String {
+    text(beams, |pos| if beams.is_energised(pos) { '#' } else { '.' })
+}
+
+pub fn paths(beams: &Beams) -> String {
+    let heads: Vec<Pos> = beams.beams().iter().map(|b| b.pos).collect();
+
+    text(beams, |pos| {
+        let visited = beams.visited[pos];
+
+        if heads.contains(&pos) {
+            '*'
+        } else if let Some(c) = beams.contraption.tiles[pos].obstacle {
+            c
+        } else if visited.count_ones() > 1 {
+            char::from_digit(visited.count_ones(), 10).unwrap()
+        } else if let Some(dir) = Direction::ALL.iter().find(|d| visited & bit(**d) != 0) {
+            dir.arrow()
+        } else {
+            '.'
+        }
+    })
+}
+
+/// A binary PPM image of @beams with every tile a square of pixels: dark for empty
+/// tiles, grey for mirrors and splitters, yellow and orange for those lit up and white
+/// where the beams are
+pub fn ppm(beams: &Beams) -> Vec<u8> {
+    let tiles = &beams.contraption.tiles;
+    let heads: Vec<Pos> = beams.beams().iter().map(|b| b.pos).collect();
+    let (width, height) = (tiles.width() * SCALE, tiles.height() * SCALE);
+    let mut image = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+
+    for y in 0..height {
+        for x in 0..width {
+            let pos = (y / SCALE, x / SCALE);
+            let rgb = match (tiles[pos].obstacle, beams.is_energised(pos)) {
+                _ if heads.contains(&pos) => [255, 255, 255],
+                (None, false) => [20, 20, 30],
+                (None, true) => [250, 200, 40],
+                (Some(_), false) => [110, 110, 120],
+                (Some(_), true) => [240, 120, 30],
+            };
+
+            image.extend(rgb);
+        }
+    }
+
+    image
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Contraption;
+
+    #[test]
+    fn pictures() {
+        let contraption = Contraption::parse(".|.\n...\n.-.\n").unwrap();
+        let mut beams = Beams::new(&contraption, ((0, 0), Direction::Right));
+
+        beams.step();
+        beams.step();
+        assert_eq!(paths(&beams), ">|.\n.*.\n.-.\n");
+
+        while beams.step() {}
+        assert_eq!(map(&beams), "##.\n.#.\n###\n");
+        assert_eq!(paths(&beams), ">|.\n.v.\n<->\n");
+
+        let image = ppm(&beams);
+        assert!(image.starts_with(b"P6\n12 12\n255\n"));
+        assert_eq!(image.len(), 13 + 12 * 12 * 3);
+    }
+}
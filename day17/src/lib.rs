@@ -0,0 +1,278 @@
+use common::{ParseError, ParseResult};
+use grid::{Direction, Grid};
+use search::Path;
+
+/// How far a crucible can move in a straight line: it has to move at least
+/// `min_steps` before it can turn or stop, and turn after at most `max_steps`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crucible {
+    pub min_steps: usize,
+    pub max_steps: usize,
+}
+
+impl Crucible {
+    pub const NORMAL: Crucible = Crucible::new(1, 3);
+    pub const ULTRA: Crucible = Crucible::new(4, 10);
+
+    pub const fn new(min_steps: usize, max_steps: usize) -> Self {
+        Crucible {
+            min_steps,
+            max_steps,
+        }
+    }
+}
+
+/// A crucible on the grid, facing @dir after moving @steps in a straight line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Vertex {
+    row: usize,
+    col: usize,
+    dir: Direction,
+    steps: usize,
+}
+
+impl Vertex {
+    fn new(row: usize, col: usize, dir: Direction, steps: usize) -> Self {
+        Vertex {
+            row,
+            col,
+            dir,
+            steps,
+        }
+    }
+
+    fn get_coords(&self) -> (usize, usize) {
+        (self.row, self.col)
+    }
+
+    /// Whether the crucible can turn to @turn_dir, which has to be a quarter turn
+    fn can_turn(&self, turn_dir: Direction, min_steps: usize) -> bool {
+        self.steps >= min_steps
+            && (turn_dir == self.dir.turn_left() || turn_dir == self.dir.turn_right())
+    }
+
+    /// Computes the vertex as if we moved 1 in @dir, having gone @steps in a straight
+    /// line, and the heat lost entering it. None if that leaves the grid.
+    fn step(&self, dir: Direction, steps: usize, weights: &Grid<u64>) -> Option<(Vertex, u64)> {
+        let (r, c) = weights.step(self.get_coords(), dir)?;
+
+        Some((Vertex::new(r, c, dir, steps), weights[(r, c)]))
+    }
+
+    /// Computes the vertex as if we turned *and moved* in turn_dir
+    fn turn(&self, turn_dir: Direction, weights: &Grid<u64>) -> Option<(Vertex, u64)> {
+        self.step(turn_dir, 1, weights)
+    }
+
+    /// Computes vertex as if we moved foward by 1 in the same direction
+    fn go_straight(&self, weights: &Grid<u64>, max_steps: usize) -> Option<(Vertex, u64)> {
+        if self.steps >= max_steps {
+            return None;
+        }
+
+        self.step(self.dir, self.steps + 1, weights)
+    }
+
+    /// Every vertex the crucible can move to next, with the heat lost getting there
+    fn successors(&self, weights: &Grid<u64>, crucible: Crucible) -> Vec<(Vertex, u64)> {
+        Direction::ALL
+            .into_iter()
+            .filter_map(|dir| {
+                if dir == self.dir {
+                    self.go_straight(weights, crucible.max_steps)
+                } else if self.can_turn(dir, crucible.min_steps) {
+                    self.turn(dir, weights)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+/// Finds the path with the least heat loss from @start to @target for @crucible
+fn find_path(
+    start: (usize, usize),
+    target: (usize, usize),
+    weights: &Grid<u64>,
+    crucible: Crucible,
+) -> Option<Path<Vertex, u64>> {
+    let starts = [Direction::Right, Direction::Down].map(|d| Vertex::new(start.0, start.1, d, 0));
+
+    search::dijkstra(
+        starts,
+        |v| v.successors(weights, crucible),
+        |v| v.get_coords() == target && v.steps >= crucible.min_steps,
+    )
+}
+
+/// The route with the least heat loss from the top left to the bottom right
+pub struct Route {
+    pub heat_loss: u64,
+    /// every block entered after the start and the direction it was entered in
+    pub moves: Vec<((usize, usize), Direction)>,
+    weights: Grid<u64>,
+}
+
+impl Route {
+    /// The map with every block on the route replaced by the direction the crucible
+    /// entered it in
+    pub fn render(&self) -> String {
+        let mut map = self
+            .weights
+            .map(|w| char::from_digit(*w as u32, 10).unwrap());
+
+        for (pos, dir) in &self.moves {
+            map[*pos] = dir.arrow();
+        }
+
+        map.to_string()
+    }
+
+    /// The heat loss and the moves as a JSON object
+    pub fn to_json(&self) -> String {
+        let moves: Vec<String> = self
+            .moves
+            .iter()
+            .map(|((row, col), dir)| {
+                format!(
+                    "{{\"row\": {}, \"col\": {}, \"dir\": \"{}\"}}",
+                    row, col, dir
+                )
+            })
+            .collect();
+
+        format!(
+            "{{\"heat_loss\": {}, \"route\": [{}]}}",
+            self.heat_loss,
+            moves.join(", ")
+        )
+    }
+}
+
+fn parse_weights(input: &str) -> ParseResult<Grid<u64>> {
+    let weights = Grid::parse(input, "a digit", |c| c.to_digit(10).map(u64::from))?;
+
+    if weights.is_empty() {
+        return Err(ParseError::missing(0, "", "a digit"));
+    }
+
+    Ok(weights)
+}
+
+/// Finds the route with the least heat loss for @crucible, None if it can't reach the
+/// bottom right
+pub fn find_route(input: &str, crucible: Crucible) -> ParseResult<Option<Route>> {
+    let weights = parse_weights(input)?;
+    let target = (weights.height() - 1, weights.width() - 1);
+
+    let Some(path) = find_path((0, 0), target, &weights, crucible) else {
+        return Ok(None);
+    };
+
+    Ok(Some(Route {
+        heat_loss: path.cost,
+        moves: path
+            .states
+            .iter()
+            .skip(1)
+            .map(|v| (v.get_coords(), v.dir))
+            .collect(),
+        weights,
+    }))
+}
+
+/// Least heat loss getting to the bottom right with @crucible, an error at the end of
+/// the input if it can't get there
+pub fn least_heat_loss(input: &str, crucible: Crucible) -> ParseResult<u64> {
+    let route = find_route(input, crucible)?.ok_or_else(|| {
+        let row = input.lines().count().saturating_sub(1);
+
+        ParseError::missing(
+            row,
+            input.lines().nth(row).unwrap_or(""),
+            "a map the crucible can cross to the bottom right",
+        )
+    })?;
+
+    Ok(route.heat_loss)
+}
+
+pub fn part1(input: &str) -> ParseResult<u64> {
+    least_heat_loss(input, Crucible::NORMAL)
+}
+
+pub fn part2(input: &str) -> ParseResult<u64> {
+    least_heat_loss(input, Crucible::ULTRA)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE1: &str = include_str!("../../inputs/day17/example1.txt");
+    const EXAMPLE2: &str = include_str!("../../inputs/day17/example2.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE1), Ok(102));
+    }
+
+    #[test]
+    fn part2_example1() {
+        assert_eq!(part2(EXAMPLE1), Ok(94));
+    }
+
+    #[test]
+    fn part2_example2() {
+        assert_eq!(part2(EXAMPLE2), Ok(71));
+    }
+
+    #[test]
+    fn no_route() {
+        assert_eq!(part1("19\n"), Ok(9));
+        assert!(find_route("19\n", Crucible::ULTRA).unwrap().is_none());
+
+        let e = part2("19\n").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 3));
+    }
+
+    #[test]
+    fn route_follows_the_rules() {
+        for crucible in [Crucible::NORMAL, Crucible::ULTRA] {
+            let route = find_route(EXAMPLE1, crucible).unwrap().unwrap();
+            let weights = parse_weights(EXAMPLE1).unwrap();
+
+            let heat_loss: u64 = route.moves.iter().map(|(pos, _)| weights[*pos]).sum();
+            assert_eq!(heat_loss, route.heat_loss);
+            assert_eq!(route.moves.last().unwrap().0, (12, 12));
+
+            // lengths of the straight runs
+            let mut runs = vec![1];
+            for ((_, d0), (_, d1)) in route.moves.iter().zip(route.moves.iter().skip(1)) {
+                if d0 == d1 {
+                    *runs.last_mut().unwrap() += 1;
+                } else {
+                    runs.push(1);
+                }
+            }
+
+            assert!(runs
+                .iter()
+                .all(|r| (crucible.min_steps..=crucible.max_steps).contains(r)));
+        }
+    }
+
+    #[test]
+    fn route_output() {
+        let route = find_route(EXAMPLE2, Crucible::new(1, 12)).unwrap().unwrap();
+
+        assert_eq!(
+            route.render(),
+            "1>>>>>>>>>>>\n99999999999v\n99999999999v\n99999999999v\n99999999999v\n"
+        );
+        assert!(route.to_json().starts_with(
+            "{\"heat_loss\": 15, \"route\": [{\"row\": 0, \"col\": 1, \"dir\": \">\"}, "
+        ));
+    }
+}
@@ -1,279 +1,98 @@
+use clap::error::ErrorKind;
 use clap::{arg, command, ArgAction};
-use std::collections::{BinaryHeap, HashMap, HashSet};
-use std::hash::Hash;
+use common::cli::{self, Input, Part};
+use day17::{find_route, Crucible};
 use std::io;
 
-/// Directions from perspective of grid
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-#[derive(Debug, Clone, Copy, Eq)]
-struct Vertex {
-    row: usize,
-    col: usize,
-    dir: Direction,
-    steps: usize,
-    cost: u64,
-}
-
-impl PartialEq for Vertex {
-    fn eq(&self, other: &Self) -> bool {
-        self.row == other.row
-            && self.col == other.col
-            && self.dir == other.dir
-            && self.steps == other.steps
-    }
-}
-
-impl Ord for Vertex {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.cost.cmp(&other.cost).reverse()
-    }
-}
-
-impl PartialOrd for Vertex {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Hash for Vertex {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.row.hash(state);
-        self.col.hash(state);
-        self.dir.hash(state);
-        self.steps.hash(state);
-        self.cost.hash(state);
-    }
-}
-
-impl Vertex {
-    fn new(row: usize, col: usize, dir: Direction, steps: usize, cost: u64) -> Self {
-        Vertex {
-            row,
-            col,
-            dir,
-            steps,
-            cost,
-        }
-    }
-
-    fn get_edges(&self, graph: &Vec<Vec<u64>>) -> Vec<(usize, usize)> {
-        let mut edges = Vec::new();
-
-        if self.row > 0 && self.dir != Direction::Down {
-            edges.push((self.row - 1, self.col));
-        }
-
-        if self.row < graph.len() - 1 && self.dir != Direction::Up {
-            edges.push((self.row + 1, self.col));
-        }
-
-        if self.col > 0 && self.dir != Direction::Right {
-            edges.push((self.row, self.col - 1));
-        }
-
-        if self.col < graph[0].len() - 1 && self.dir != Direction::Left {
-            edges.push((self.row, self.col + 1));
-        }
-
-        edges
-    }
-
-    fn get_direction(&self, v1: (usize, usize)) -> Direction {
-        let (dr, dc) = (v1.0 as i64 - self.row as i64, v1.1 as i64 - self.col as i64);
-
-        match dr {
-            -1 => Direction::Up,
-            1 => Direction::Down,
-            _ => match dc {
-                -1 => Direction::Left,
-                1 => Direction::Right,
-                _ => panic!("invalid direction!"),
-            },
+/// Prints the least heat loss for @crucible as the answer to part @n (or on its own
+/// without a part), followed by the route if @route. With @json only the route is
+/// printed, as JSON.
+fn print_crucible(n: Option<u32>, input: &Input, crucible: Crucible, route: bool, json: bool) {
+    let found = match find_route(&input.text, crucible) {
+        Ok(Some(found)) => found,
+        Ok(None) => {
+            eprintln!(
+                "error: a crucible moving {} to {} blocks in a line can't reach the bottom right",
+                crucible.min_steps, crucible.max_steps
+            );
+            std::process::exit(1);
         }
-    }
-
-    fn get_coords(&self) -> (usize, usize) {
-        (self.row, self.col)
-    }
-
-    fn can_turn(&self, turn_dir: Direction, weights: &Vec<Vec<u64>>) -> bool {
-        use Direction::*;
+        Err(e) => return cli::print_answer::<u64>(n.unwrap_or(1), input, Err(e)),
+    };
 
-        if self.steps < 4 {
-            return false;
-        }
-
-        match (self.dir, turn_dir) {
-            (Up, Right) | (Down, Right) => self.col < weights[0].len() - 1,
-            (Up, Left) | (Down, Left) => self.col > 0,
-            (Right, Up) | (Left, Up) => self.row > 0,
-            (Right, Down) | (Left, Down) => self.row < weights.len(),
-            _ => false,
-        }
+    if json {
+        println!("{}", found.to_json());
+        return;
     }
 
-    /// Computes the vertex as if we turned *and moved* in turn_dir
-    fn turn(&self, turn_dir: Direction, weights: &[Vec<u64>]) -> Vertex {
-        use Direction::*;
-
-        match turn_dir {
-            Up => Vertex::new(
-                self.row - 1,
-                self.col,
-                Up,
-                1,
-                self.cost + weights[self.row - 1][self.col],
-            ),
-            Down => Vertex::new(
-                self.row + 1,
-                self.col,
-                Down,
-                1,
-                self.cost + weights[self.row + 1][self.col],
-            ),
-            Left => Vertex::new(
-                self.row,
-                self.col - 1,
-                Left,
-                1,
-                self.cost + weights[self.row][self.col - 1],
-            ),
-            Right => Vertex::new(
-                self.row,
-                self.col + 1,
-                Right,
-                1,
-                self.cost + weights[self.row][self.col + 1],
-            ),
-        }
+    match n {
+        Some(n) => cli::print_answer(n, input, Ok(found.heat_loss)),
+        None => println!("heat loss: {}", found.heat_loss),
     }
 
-    /// Computes vertex as if we moved foward by 1 in the same direction
-    fn go_straight(&self, weights: &Vec<Vec<u64>>) -> Option<Vertex> {
-        use Direction::*;
-
-        if self.steps >= 10 {
-            return None;
-        }
-
-        let (r, c) = match self.dir {
-            Up => {
-                if self.row == 0 {
-                    return None;
-                } else {
-                    (self.row - 1, self.col)
-                }
-            }
-            Down => {
-                if self.row >= weights.len() - 1 {
-                    return None;
-                } else {
-                    (self.row + 1, self.col)
-                }
-            }
-            Left => {
-                if self.col == 0 {
-                    return None;
-                } else {
-                    (self.row, self.col - 1)
-                }
-            }
-            Right => {
-                if self.col >= weights[0].len() - 1 {
-                    return None;
-                } else {
-                    (self.row, self.col + 1)
-                }
-            }
-        };
-
-        Some(Vertex::new(
-            r,
-            c,
-            self.dir,
-            self.steps + 1,
-            self.cost + weights[r][c],
-        ))
+    if route {
+        print!("{}", found.render());
     }
 }
 
 fn main() -> Result<(), io::Error> {
-    let input = include_str!("../../input.txt");
-    let example = include_str!("../../example.txt");
-
-    let matches = command!()
-        .arg(arg!(example: -e).action(ArgAction::SetTrue))
-        .get_matches();
-
-    if matches.get_flag("example") {
-        println!("solution (example): {}", solution(example));
-    } else {
-        println!("solution: {}", solution(input));
-    }
-
-    Ok(())
-}
-
-fn find_path(start: (usize, usize), target: (usize, usize), weights: &Vec<Vec<u64>>) -> u64 {
-    let mut queue: BinaryHeap<Vertex> = BinaryHeap::new();
-    let mut dist: HashMap<(usize, usize), u64> = HashMap::new();
-    let mut seen: HashSet<((usize, usize), Direction, usize)> = HashSet::new();
-
-    queue.push(Vertex::new(start.0, start.1, Direction::Right, 0, 0));
-    queue.push(Vertex::new(start.0, start.1, Direction::Down, 0, 0));
-
-    while !queue.is_empty() {
-        let u = queue.pop().unwrap();
-        if seen.get(&(u.get_coords(), u.dir, u.steps)).is_some() {
-            continue;
-        } else {
-            seen.insert((u.get_coords(), u.dir, u.steps));
+    let mut command = command!()
+        .args(cli::input_args())
+        .arg(cli::part_arg())
+        .arg(
+            arg!(min: --min <N> "Run one crucible that has to move at least N blocks in a line")
+                .value_parser(clap::value_parser!(usize)),
+        )
+        .arg(
+            arg!(max: --max <N> "Run one crucible that can move at most N blocks in a line")
+                .value_parser(clap::value_parser!(usize)),
+        )
+        .arg(
+            arg!(route: --route "Print the route on the map after the answer")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            arg!(json: --json "Print the route as JSON instead of the answer")
+                .action(ArgAction::SetTrue),
+        );
+    let matches = command.get_matches_mut();
+
+    let input = cli::read_input(17, &matches)?;
+    let part = *matches.get_one::<Part>("part").unwrap();
+    let route = matches.get_flag("route");
+    let json = matches.get_flag("json");
+
+    let min = matches.get_one::<usize>("min");
+    let max = matches.get_one::<usize>("max");
+
+    // a custom crucible replaces both parts, filling in what wasn't given from part 1's
+    if min.is_some() || max.is_some() {
+        let min = min.copied().unwrap_or(Crucible::NORMAL.min_steps);
+        let max = max.copied().unwrap_or(Crucible::NORMAL.max_steps.max(min));
+
+        if max == 0 || min > max {
+            command
+                .error(
+                    ErrorKind::ValueValidation,
+                    format!(
+                        "--max must be at least 1 and at least --min, got {}..{}",
+                        min, max
+                    ),
+                )
+                .exit();
         }
 
-        for e in u.get_edges(weights) {
-            let e_dir = u.get_direction(e);
-
-            if u.dir != e_dir && u.can_turn(e_dir, weights) {
-                queue.push(u.turn(e_dir, weights));
-            }
-
-            if let Some(v) = u.go_straight(weights) {
-                if v.get_coords() == target && v.steps < 4 {
-                    continue;
-                }
-                dist.entry(v.get_coords())
-                    .and_modify(|c| {
-                        if v.cost < *c {
-                            *c = v.cost
-                        }
-                    })
-                    .or_insert(v.cost);
-
-                queue.push(v);
-            }
-        }
+        print_crucible(None, &input, Crucible::new(min, max), route, json);
+        return Ok(());
     }
 
-    *dist.get(&target).unwrap()
-}
-
-fn solution(input: &str) -> u64 {
-    let lines: Vec<_> = input.lines().collect();
-    let mut weights: Vec<Vec<u64>> = Vec::new();
+    if part.runs_part1() {
+        print_crucible(Some(1), &input, Crucible::NORMAL, route, json);
+    }
 
-    for (i, line) in lines.iter().enumerate() {
-        weights.push(Vec::new());
-        for w in line.chars().filter_map(|c| c.to_digit(10)) {
-            weights[i].push(w as u64);
-        }
+    if part.runs_part2() {
+        print_crucible(Some(2), &input, Crucible::ULTRA, route, json);
     }
 
-    find_path((0, 0), (weights.len() - 1, weights[0].len() - 1), &weights)
+    Ok(())
 }
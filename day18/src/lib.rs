@@ -0,0 +1,252 @@
+use common::error::parse_num;
+use common::{ParseError, ParseResult};
+use grid::{Direction, Point, Polygon};
+use std::fmt::Write;
+
+/// Dig @len meters towards @dir
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub dir: Direction,
+    pub len: i64,
+}
+
+/// Both ways of reading the dig plan, one instruction per line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigPlan {
+    /// from the direction and length fields, like "R 6"
+    pub plain: Vec<Instruction>,
+    /// decoded from the colour codes, like "(#70c710)"
+    pub decoded: Vec<Instruction>,
+    /// colour codes as 0xRRGGBB, which are the colours of the plain instructions
+    pub colours: Vec<u32>,
+}
+
+impl DigPlan {
+    pub fn parse(input: &str) -> ParseResult<Self> {
+        let mut plan = DigPlan {
+            plain: Vec::new(),
+            decoded: Vec::new(),
+            colours: Vec::new(),
+        };
+
+        for (row, line) in input.lines().enumerate() {
+            let (dir, len, hex) = split_line(row, line)?;
+            let colour = parse_colour(row, line, hex)?;
+
+            plan.plain.push(Instruction {
+                dir: Direction::parse(row, line, dir)?,
+                len: parse_num::<u32>(row, line, len)? as i64,
+            });
+            plan.decoded.push(decode_colour(row, line, hex, colour)?);
+            plan.colours.push(colour);
+        }
+
+        Ok(plan)
+    }
+}
+
+/// Splits the @row'th @line into its direction, length and colour
+fn split_line(row: usize, line: &str) -> ParseResult<(&str, &str, &str)> {
+    let mut parts = line.split_whitespace();
+
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(dir), Some(num), Some(hex), None) => Ok((dir, num, hex)),
+        (_, _, _, Some(extra)) => Err(ParseError::new(row, line, extra, "end of line")),
+        _ => Err(ParseError::missing(
+            row,
+            line,
+            "\"<direction> <length> <colour>\"",
+        )),
+    }
+}
+
+/// Reads @hex, the colour code in the @row'th @line
+fn parse_colour(row: usize, line: &str, hex: &str) -> ParseResult<u32> {
+    let digits = hex
+        .strip_prefix("(#")
+        .and_then(|h| h.strip_suffix(')'))
+        .filter(|h| h.len() == 6)
+        .ok_or_else(|| ParseError::new(row, line, hex, "\"(#<6 hex digits>)\""))?;
+
+    if let Some(bad) = digits.matches(|c: char| !c.is_ascii_hexdigit()).next() {
+        return Err(ParseError::new(row, line, bad, "a hex digit"));
+    }
+
+    Ok(u32::from_str_radix(digits, 16).unwrap())
+}
+
+/// Decodes the instruction hidden in @colour: the first 5 hex digits are the length
+/// and the last the direction
+fn decode_colour(row: usize, line: &str, hex: &str, colour: u32) -> ParseResult<Instruction> {
+    let dir = match colour & 0xf {
+        0 => Direction::Right,
+        1 => Direction::Down,
+        2 => Direction::Left,
+        3 => Direction::Up,
+        _ => {
+            // the last digit, before the ")"
+            let d = &hex[hex.len() - 2..hex.len() - 1];
+
+            return Err(ParseError::new(row, line, d, "a direction from 0 to 3"));
+        }
+    };
+
+    Ok(Instruction {
+        dir,
+        len: (colour >> 4) as i64,
+    })
+}
+
+/// The outline of the trench dug by following @instructions from (0, 0), through the
+/// middle of every cube dug out
+pub fn trench(instructions: &[Instruction]) -> Polygon {
+    let mut pos = Point::default();
+
+    Polygon::new(
+        instructions
+            .iter()
+            .map(|i| {
+                pos += i.dir.delta() * i.len;
+                pos
+            })
+            .collect(),
+    )
+}
+
+/// The trench of the plain instructions as an SVG, with the lagoon filled in and
+/// every stretch of trench drawn in its colour
+pub fn svg(plan: &DigPlan) -> String {
+    let polygon = trench(&plan.plain);
+    let corners = polygon.corners();
+    let (min_x, max_x) = (
+        corners.iter().map(|p| p.x).min(),
+        corners.iter().map(|p| p.x).max(),
+    );
+    let (min_y, max_y) = (
+        corners.iter().map(|p| p.y).min(),
+        corners.iter().map(|p| p.y).max(),
+    );
+    let (min_x, max_x) = (min_x.unwrap_or(0), max_x.unwrap_or(0));
+    let (min_y, max_y) = (min_y.unwrap_or(0), max_y.unwrap_or(0));
+
+    let mut s = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+        min_x - 1,
+        min_y - 1,
+        max_x - min_x + 2,
+        max_y - min_y + 2
+    );
+
+    let points: Vec<String> = corners.iter().map(|p| format!("{},{}", p.x, p.y)).collect();
+    writeln!(
+        s,
+        "  <polygon points=\"{}\" fill=\"#d8d0c0\" stroke=\"none\"/>",
+        points.join(" ")
+    )
+    .unwrap();
+
+    // each stretch goes from the corner before it, the last corner for the first one
+    let starts = corners.iter().cycle().skip(corners.len().saturating_sub(1));
+
+    for ((from, to), colour) in starts.zip(corners).zip(&plan.colours) {
+        writeln!(
+            s,
+            "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#{:06x}\" \
+             stroke-width=\"1\" stroke-linecap=\"square\"/>",
+            from.x, from.y, to.x, to.y, colour
+        )
+        .unwrap();
+    }
+
+    s + "</svg>\n"
+}
+
+/// Cubes dug out by following @instructions, the @input they are from, and digging
+/// out the inside of the trench. An error if the trench goes back over itself, which
+/// leaves no well defined inside.
+fn lagoon(input: &str, instructions: &[Instruction]) -> ParseResult<u64> {
+    let trench = trench(instructions);
+
+    if let Some(edge) = trench.overlapping_edge() {
+        // edge i runs from the end of instruction i to the end of the next one
+        let row = (edge + 1) % instructions.len();
+        let line = input.lines().nth(row).unwrap();
+
+        return Err(ParseError::new(
+            row,
+            line,
+            line,
+            "an instruction that doesn't dig through the trench again",
+        ));
+    }
+
+    Ok(trench.lattice_points())
+}
+
+pub fn part1(input: &str) -> ParseResult<u64> {
+    lagoon(input, &DigPlan::parse(input)?.plain)
+}
+
+pub fn part2(input: &str) -> ParseResult<u64> {
+    lagoon(input, &DigPlan::parse(input)?.decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grid::polygon::Containment;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day18/example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE), Ok(62));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), Ok(952408144115));
+    }
+
+    #[test]
+    fn plan() {
+        let plan = DigPlan::parse(EXAMPLE).unwrap();
+        let first = |dir, len| Instruction { dir, len };
+
+        assert_eq!(plan.plain[0], first(Direction::Right, 6));
+        assert_eq!(plan.decoded[0], first(Direction::Right, 461937));
+        assert_eq!(plan.colours[0], 0x70c710);
+
+        let lagoon = trench(&plan.plain);
+        assert_eq!(lagoon.boundary_points(), 38);
+        assert_eq!(lagoon.interior_points(), 24);
+        assert_eq!(lagoon.contains(Point::new(1, 1)), Containment::Inside);
+        assert_eq!(lagoon.contains(Point::new(6, 0)), Containment::Boundary);
+        assert_eq!(lagoon.contains(Point::new(0, 3)), Containment::Outside);
+
+        let e = DigPlan::parse("R 6 (#70c714)").unwrap_err();
+        assert_eq!((e.column, e.token.as_str()), (12, "4"));
+    }
+
+    #[test]
+    fn doubling_back() {
+        let e = part1("R 1 (#000010)\nR 1 (#000010)\nL 2 (#000022)\n").unwrap_err();
+        assert_eq!((e.line, e.column), (3, 1));
+
+        // R 2, L 2 through the colours, where the edge back to the start is the first
+        // instruction's and is checked last
+        let e = part2("R 1 (#000020)\nR 1 (#000022)\n").unwrap_err();
+        assert_eq!(e.line, 1);
+    }
+
+    #[test]
+    fn drawing() {
+        let svg = svg(&DigPlan::parse(EXAMPLE).unwrap());
+
+        assert!(
+            svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-1 -1 8 11\">")
+        );
+        assert!(svg.contains("<line x1=\"0\" y1=\"0\" x2=\"6\" y2=\"0\" stroke=\"#70c710\""));
+        assert_eq!(svg.matches("<line").count(), 14);
+    }
+}
@@ -0,0 +1,500 @@
+use common::error::{parse_num, split_once};
+use common::{ParseError, ParseResult};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Rule {
+    /// index of the rating the rule checks, see `ch_to_idx`
+    idx: usize,
+    comp: Ordering,
+    val: u64,
+}
+
+impl Rule {
+    fn new(idx: usize, comp: char, val: u64) -> Self {
+        let rcomp = match comp {
+            '<' => Ordering::Less,
+            '>' => Ordering::Greater,
+            _ => Ordering::Equal,
+        };
+
+        Rule {
+            idx,
+            comp: rcomp,
+            val,
+        }
+    }
+}
+
+/// What finally happens to a part
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Accept,
+    Reject,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Accept => write!(f, "A"),
+            Verdict::Reject => write!(f, "R"),
+        }
+    }
+}
+
+/// Where a rule sends a part: straight to a verdict or on to another workflow
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Dest {
+    Verdict(Verdict),
+    Workflow(String),
+}
+
+impl Dest {
+    fn new(name: &str) -> Self {
+        match name {
+            "A" => Dest::Verdict(Verdict::Accept),
+            "R" => Dest::Verdict(Verdict::Reject),
+            _ => Dest::Workflow(name.to_string()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Workflow {
+    name: String,
+    rules: Vec<(Rule, Dest)>,
+    /// where the parts no rule matches go
+    end: Dest,
+}
+
+impl Workflow {
+    fn new(name: &str, end: &str) -> Self {
+        Workflow {
+            name: name.to_string(),
+            rules: Vec::new(),
+            end: Dest::new(end),
+        }
+    }
+
+    fn add_rule(&mut self, rule: Rule, dest: &str) {
+        self.rules.push((rule, Dest::new(dest)));
+    }
+
+    /// Where this workflow sends @part: the destination of the first rule it matches
+    pub fn evaluate(&self, part: &Part) -> &Dest {
+        self.rules
+            .iter()
+            .find(|(r, _)| part.ratings[r.idx].cmp(&r.val) == r.comp)
+            .map_or(&self.end, |(_, d)| d)
+    }
+
+    /// Names of the workflows this one can send parts to
+    fn next(&self) -> impl Iterator<Item = &str> {
+        self.rules
+            .iter()
+            .map(|(_, d)| d)
+            .chain([&self.end])
+            .filter_map(|d| match d {
+                Dest::Workflow(name) => Some(name.as_str()),
+                Dest::Verdict(_) => None,
+            })
+    }
+}
+
+fn ch_to_idx(ch: &str) -> Option<usize> {
+    match ch {
+        "x" => Some(0),
+        "m" => Some(1),
+        "a" => Some(2),
+        "s" => Some(3),
+        _ => None,
+    }
+}
+
+/// Parses @token, a slice of the @row'th @line, as the name of a rating
+fn parse_rating(row: usize, line: &str, token: &str) -> ParseResult<usize> {
+    ch_to_idx(token).ok_or_else(|| ParseError::new(row, line, token, "x, m, a or s"))
+}
+
+/// Splits the first character off @s
+fn split_first(s: &str) -> (&str, &str) {
+    s.split_at(s.chars().next().map_or(0, |c| c.len_utf8()))
+}
+
+/// Parses a rule of the form `a<2006:qkq`, returning it and its destination
+fn parse_rule<'a>(row: usize, line: &str, rule: &'a str) -> ParseResult<(Rule, &'a str)> {
+    let (cond, dest) = rule
+        .split_once(':')
+        .ok_or_else(|| ParseError::new(row, line, rule, "\"<rating><op><value>:<dest>\""))?;
+    let (var, rest) = split_first(cond);
+    let (comp, val) = split_first(rest);
+
+    let idx = parse_rating(row, line, var)?;
+    if comp != "<" && comp != ">" {
+        return Err(ParseError::new(row, line, comp, "\"<\" or \">\""));
+    }
+
+    Ok((
+        Rule::new(
+            idx,
+            comp.chars().next().unwrap(),
+            parse_num(row, line, val)?,
+        ),
+        dest,
+    ))
+}
+
+/// A part and its x, m, a and s ratings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Part {
+    pub ratings: [u64; 4],
+}
+
+impl Part {
+    /// Parses a part of the form `{x=787,m=2655,a=1222,s=2876}`
+    pub fn parse(row: usize, line: &str) -> ParseResult<Self> {
+        let mut ratings = [0; 4];
+        let inner = line
+            .strip_prefix('{')
+            .and_then(|l| l.strip_suffix('}'))
+            .ok_or_else(|| ParseError::new(row, line, line, "\"{<ratings>}\""))?;
+
+        for rating in inner.split(',') {
+            let (ch, val) = rating
+                .split_once('=')
+                .ok_or_else(|| ParseError::new(row, line, rating, "\"<rating>=<value>\""))?;
+
+            ratings[parse_rating(row, line, ch)?] = parse_num(row, line, val)?;
+        }
+
+        Ok(Part { ratings })
+    }
+
+    /// Sum of the ratings
+    pub fn total(&self) -> u64 {
+        self.ratings.iter().sum()
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [x, m, a, s] = self.ratings;
+
+        write!(f, "{{x={},m={},a={},s={}}}", x, m, a, s)
+    }
+}
+
+/// Every workflow by name, starting from "in"
+#[derive(Debug)]
+pub struct Workflows {
+    flows: HashMap<String, Workflow>,
+}
+
+impl Workflows {
+    /// Parses one workflow per line of @input. Every destination has to be A, R or a
+    /// workflow, and no workflow may lead back to itself, or parts would go round
+    /// forever.
+    pub fn parse(input: &[&str]) -> ParseResult<Self> {
+        let mut flows = HashMap::new();
+        // every (row, line, workflow, destination) a rule sends parts to, checked once
+        // all the workflows are known
+        let mut dests = Vec::new();
+
+        for (row, flow) in input.iter().copied().enumerate() {
+            let (name, rules) = split_once(row, flow, "{")?;
+            let rules = rules
+                .strip_suffix('}')
+                .ok_or_else(|| ParseError::missing(row, flow, "\"}\""))?;
+            let rules: Vec<&str> = rules.split(',').collect();
+
+            if flows.contains_key(name) {
+                return Err(ParseError::new(row, flow, name, "a new workflow name"));
+            }
+
+            let end = rules[rules.len() - 1];
+            let mut wf = Workflow::new(name, end);
+            for r in rules.iter().take(rules.len() - 1) {
+                let (rule, dest) = parse_rule(row, flow, r)?;
+
+                wf.add_rule(rule, dest);
+                dests.push((row, flow, name, dest));
+            }
+
+            dests.push((row, flow, name, end));
+
+            flows.insert(wf.name.clone(), wf);
+        }
+
+        for &(row, line, _, dest) in &dests {
+            if dest != "A" && dest != "R" && !flows.contains_key(dest) {
+                return Err(ParseError::new(row, line, dest, "A, R or a workflow"));
+            }
+        }
+
+        if !flows.contains_key("in") {
+            let row = input.len().saturating_sub(1);
+
+            return Err(ParseError::missing(
+                row,
+                input.get(row).unwrap_or(&""),
+                "a workflow named in",
+            ));
+        }
+
+        let flows = Workflows { flows };
+
+        if let Some((from, to)) = flows.find_cycle() {
+            let &(row, line, _, dest) = dests
+                .iter()
+                .find(|&&(_, _, name, dest)| name == from && dest == to)
+                .unwrap();
+
+            return Err(ParseError::new(
+                row,
+                line,
+                dest,
+                format!("a destination that doesn't lead back to {}", to),
+            ));
+        }
+
+        Ok(flows)
+    }
+
+    /// The workflow @from and destination @to that close a cycle, if there is one.
+    /// Goes depth first from "in" and then every other workflow in turn: a cycle closes
+    /// when a destination is still on the stack.
+    fn find_cycle(&self) -> Option<(&str, &str)> {
+        let mut on_stack = HashSet::new();
+        let mut done = HashSet::new();
+        let mut names: Vec<&str> = self.flows.keys().map(|k| k.as_str()).collect();
+        names.sort_unstable_by_key(|&name| (name != "in", name));
+
+        for start in names {
+            if done.contains(start) {
+                continue;
+            }
+
+            let mut stack = vec![(start, self.flows[start].next().collect::<Vec<_>>())];
+            on_stack.insert(start);
+
+            while let Some((name, next)) = stack.last_mut() {
+                let name = *name;
+
+                match next.pop() {
+                    Some(to) if on_stack.contains(to) => return Some((name, to)),
+                    Some(to) if !done.contains(to) => {
+                        on_stack.insert(to);
+                        stack.push((to, self.flows[to].next().collect()));
+                    }
+                    Some(_) => (),
+                    None => {
+                        on_stack.remove(name);
+                        done.insert(name);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Names of the workflows no part can get to from "in", sorted
+    pub fn unreachable(&self) -> Vec<&str> {
+        let mut reached = HashSet::from(["in"]);
+        let mut queue = vec!["in"];
+
+        while let Some(name) = queue.pop() {
+            for next in self.flows[name].next() {
+                if reached.insert(next) {
+                    queue.push(next);
+                }
+            }
+        }
+
+        let mut names: Vec<&str> = self
+            .flows
+            .keys()
+            .map(|k| k.as_str())
+            .filter(|k| !reached.contains(k))
+            .collect();
+        names.sort_unstable();
+
+        names
+    }
+
+    /// Sends @part through the workflows from "in", returning its verdict and the
+    /// names of the workflows it went through
+    pub fn evaluate(&self, part: &Part) -> (Verdict, Vec<&str>) {
+        let mut trace = vec!["in"];
+
+        loop {
+            match self.flows[trace[trace.len() - 1]].evaluate(part) {
+                Dest::Verdict(v) => return (*v, trace),
+                Dest::Workflow(name) => trace.push(name),
+            }
+        }
+    }
+
+    /// Number of combinations of ratings that are accepted, with every rating going
+    /// from the first to the second (inclusive) of its @bounds. Each range can hold at
+    /// most 65535 values for the count to fit in a u64.
+    pub fn count_accepted(&self, bounds: [(u64, u64); 4]) -> u64 {
+        let ranges = bounds.map(|(min, max)| [min, max.saturating_add(1).max(min)]);
+
+        run_rules(&Dest::new("in"), ranges, &self.flows)
+    }
+}
+
+/// Number of combinations in @ranges (each from [0] up to but not including [1]) that
+/// end up accepted from @dest
+fn run_rules(dest: &Dest, mut ranges: [[u64; 2]; 4], flows: &HashMap<String, Workflow>) -> u64 {
+    let name = match dest {
+        Dest::Verdict(Verdict::Accept) => {
+            return ranges
+                .into_iter()
+                .map(|r| r[1].saturating_sub(r[0]))
+                .product()
+        }
+        Dest::Verdict(Verdict::Reject) => return 0,
+        Dest::Workflow(name) => name,
+    };
+
+    let mut t = 0;
+    if let Some(flow) = flows.get(name) {
+        for (r, d) in flow.rules.iter() {
+            let range = ranges[r.idx];
+
+            match ((range[0], range[1]), r.comp) {
+                ((_, u), Ordering::Less) if u <= r.val => {
+                    return t + run_rules(d, ranges, flows);
+                }
+                ((l, _), Ordering::Greater) if l > r.val => {
+                    return t + run_rules(d, ranges, flows);
+                }
+                ((l, u), Ordering::Less) if l < r.val => {
+                    ranges[r.idx] = [l, r.val];
+                    t += run_rules(d, ranges, flows);
+                    ranges[r.idx] = [r.val, u];
+                }
+                // nothing is above the largest u64, and then there is nothing to split
+                ((l, u), Ordering::Greater) if u > r.val.saturating_add(1) => {
+                    ranges[r.idx] = [r.val + 1, u];
+                    t += run_rules(d, ranges, flows);
+                    ranges[r.idx] = [l, r.val + 1];
+                }
+                _ => (),
+            }
+        }
+
+        return t + run_rules(&flow.end, ranges, flows);
+    }
+    0
+}
+
+/// The workflows and the parts in @input, which are separated by an empty line
+pub fn parse_input(input: &str) -> ParseResult<(Workflows, Vec<Part>)> {
+    let lines: Vec<_> = input.lines().collect();
+    let (flows, parts) = match lines.iter().position(|l| l.is_empty()) {
+        Some(i) => (&lines[..i], &lines[(i + 1)..]),
+        None => (&lines[..], &lines[lines.len()..]),
+    };
+    let first_part = lines.len() - parts.len();
+
+    Ok((
+        Workflows::parse(flows)?,
+        parts
+            .iter()
+            .enumerate()
+            .map(|(i, p)| Part::parse(first_part + i, p))
+            .collect::<ParseResult<_>>()?,
+    ))
+}
+
+/// The puzzle's ratings go from 1 to 4000
+pub const BOUNDS: [(u64, u64); 4] = [(1, 4000); 4];
+
+pub fn part1(input: &str) -> ParseResult<u64> {
+    let (flows, parts) = parse_input(input)?;
+
+    Ok(parts
+        .iter()
+        .filter(|p| flows.evaluate(p).0 == Verdict::Accept)
+        .map(|p| p.total())
+        .sum())
+}
+
+pub fn part2(input: &str) -> ParseResult<u64> {
+    let (flows, _) = parse_input(input)?;
+
+    Ok(flows.count_accepted(BOUNDS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day19/example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE), Ok(19114));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), Ok(167409079868000));
+    }
+
+    #[test]
+    fn traces() {
+        let (flows, parts) = parse_input(EXAMPLE).unwrap();
+
+        assert_eq!(
+            flows.evaluate(&parts[0]),
+            (Verdict::Accept, vec!["in", "qqz", "qs", "lnx"])
+        );
+        assert_eq!(
+            flows.evaluate(&parts[1]),
+            (Verdict::Reject, vec!["in", "px", "rfg", "gd"])
+        );
+        assert_eq!(parts[0].to_string(), "{x=787,m=2655,a=1222,s=2876}");
+        assert!(flows.unreachable().is_empty());
+    }
+
+    #[test]
+    fn bounds() {
+        let flows = Workflows::parse(&["in{x<3:A,m>1:R,A}"]).unwrap();
+
+        // x is 1 or 2, or else m is 1
+        assert_eq!(flows.count_accepted([(1, 4); 4]), 2 * 64 + 2 * 16);
+        assert_eq!(flows.count_accepted([(3, 4), (2, 4), (1, 1), (1, 1)]), 0);
+        assert_eq!(flows.count_accepted([(3, 4), (1, 1), (1, 2), (1, 2)]), 8);
+        assert_eq!(flows.count_accepted([(5, 4); 4]), 0);
+
+        // rules at the very top of the ratings
+        let flows = Workflows::parse(&["in{x>65535:R,m>18446744073709551615:R,A}"]).unwrap();
+        assert_eq!(flows.count_accepted([(65535, 65535); 4]), 1);
+        assert_eq!(flows.count_accepted([(1, 3); 4]), 81);
+    }
+
+    #[test]
+    fn validation() {
+        let e = Workflows::parse(&["in{x<3:ab,R}", "ab{m>1:cd,A}", "cd{a<5:R,in}"]).unwrap_err();
+        assert_eq!((e.line, e.column, e.token.as_str()), (3, 10, "in"));
+
+        let e = Workflows::parse(&["in{x<3:A,R}", "ab{m>1:ab,A}"]).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 8));
+
+        let e = Workflows::parse(&["in{x<3:zz,R}"]).unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.expected.as_str()),
+            (1, 8, "A, R or a workflow")
+        );
+
+        let flows = Workflows::parse(&["in{x<3:A,R}", "ab{m>1:cd,A}", "cd{A}"]).unwrap();
+        assert_eq!(flows.unreachable(), ["ab", "cd"]);
+    }
+}
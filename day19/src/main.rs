@@ -1,177 +1,85 @@
+use clap::error::ErrorKind;
 use clap::{arg, command, ArgAction};
-use std::cmp::Ordering;
-use std::collections::HashMap;
+use common::cli::{self, Part};
+use day19::{parse_input, part1, part2, BOUNDS};
 use std::io;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Rule {
-    ch: char,
-    comp: Ordering,
-    val: u64,
-}
-
-impl Rule {
-    fn new(ch: char, comp: char, val: u64) -> Self {
-        let rcomp = match comp {
-            '<' => Ordering::Less,
-            '>' => Ordering::Greater,
-            _ => Ordering::Equal,
-        };
-
-        Rule {
-            ch,
-            comp: rcomp,
-            val,
-        }
-    }
-}
-
-#[derive(Debug)]
-struct Workflow {
-    name: String,
-    start: char,
-    rules: Vec<(Rule, String)>,
-    end: String,
-}
-
-impl Workflow {
-    fn new(name: &str) -> Self {
-        Workflow {
-            name: name.to_string(),
-            start: '0',
-            rules: Vec::new(),
-            end: String::new(),
-        }
-    }
-
-    fn add_rule(&mut self, rule: Rule, dest: &str) {
-        if self.rules.is_empty() {
-            self.start = rule.ch;
-        }
-
-        self.rules.push((rule, dest.to_string()));
-    }
-
-    fn set_end(&mut self, end: &str) {
-        self.end = end.to_string();
-    }
-}
-
 fn main() -> Result<(), io::Error> {
-    let input = include_str!("../../input.txt");
-    let example = include_str!("../../example.txt");
-
-    let matches = command!()
-        .arg(arg!(example: -e).action(ArgAction::SetTrue))
-        .get_matches();
-
-    if matches.get_flag("example") {
-        println!("solution (example): {}", solution(example));
-    } else {
-        println!("solution: {}", solution(input));
-    }
-
-    Ok(())
-}
-
-fn ch_to_idx(ch: char) -> usize {
-    match ch {
-        'x' => 0,
-        'm' => 1,
-        'a' => 2,
-        's' => 3,
-        _ => panic!("Invalid char for ch_to_idx conversion {}!", ch),
-    }
-}
-
-fn parse_flows(input: &[&str]) -> HashMap<String, Workflow> {
-    let mut flows = HashMap::new();
-
-    for flow in input {
-        let parts: Vec<&str> = flow.split('{').collect();
-        let name = parts[0];
-        let rules: Vec<&str> = parts[1].split(',').collect();
-
-        let mut wf = Workflow::new(name);
-        for r in rules.iter().take(rules.len() - 1) {
-            let var = r.chars().next().unwrap();
-            let comp = r.chars().nth(1).unwrap();
-            let val: String = r.chars().skip(2).take_while(|c| *c != ':').collect();
-            let rule = Rule::new(var, comp, val.parse::<u64>().unwrap());
-
-            let dest: String = r.chars().rev().take_while(|c| *c != ':').collect();
-            let dest: String = dest.chars().rev().collect();
-            wf.add_rule(rule, &dest);
-        }
-
-        let end: String = rules[rules.len() - 1]
-            .chars()
-            .take_while(|c| *c != '}')
-            .collect();
-        wf.set_end(&end);
-
-        flows.insert(wf.name.clone(), wf);
-    }
+    let mut command = command!()
+        .args(cli::input_args())
+        .arg(cli::part_arg())
+        .arg(
+            arg!(trace: --trace "Print every part with the workflows it goes through instead of the answers")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            arg!(min: --min <N> "Count the accepted combinations with every rating at least N")
+                .value_parser(clap::value_parser!(u64).range(1..=65535)),
+        )
+        .arg(
+            arg!(max: --max <N> "Count the accepted combinations with every rating at most N")
+                .value_parser(clap::value_parser!(u64).range(1..=65535)),
+        )
+        .arg(
+            arg!(check: --check "List the workflows no part can get to instead of the answers")
+                .action(ArgAction::SetTrue),
+        );
+    let matches = command.get_matches_mut();
+
+    let input = cli::read_input(19, &matches)?;
+    let part = *matches.get_one::<Part>("part").unwrap();
+
+    let min = matches.get_one::<u64>("min");
+    let max = matches.get_one::<u64>("max");
+
+    if matches.get_flag("trace") || matches.get_flag("check") || min.is_some() || max.is_some() {
+        let (flows, parts) = match parse_input(&input.text) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                cli::print_answer::<u64>(1, &input, Err(e));
+                return Ok(());
+            }
+        };
 
-    flows
-}
+        if matches.get_flag("check") {
+            let unreachable = flows.unreachable();
 
-fn run_rules(dest: &str, mut ranges: [[u64; 2]; 4], flows: &HashMap<String, Workflow>) -> u64 {
-    match dest {
-        "A" => {
-            return ranges
-                .into_iter()
-                .map(|r| r[1].saturating_sub(r[0]))
-                .product()
+            if unreachable.is_empty() {
+                println!("every workflow is reachable from in");
+            } else {
+                println!("unreachable: {}", unreachable.join(", "));
+            }
         }
-        "R" => return 0,
-        _ => (),
-    }
 
-    let mut t = 0;
-    if let Some(flow) = flows.get(dest) {
-        for (r, d) in flow.rules.iter() {
-            let range = ranges[ch_to_idx(r.ch)];
+        if matches.get_flag("trace") {
+            for p in &parts {
+                let (verdict, trace) = flows.evaluate(p);
 
-            match ((range[0], range[1]), r.comp) {
-                ((_, u), Ordering::Less) if u <= r.val => {
-                    return t + run_rules(d, ranges, flows);
-                }
-                ((l, _), Ordering::Greater) if l > r.val => {
-                    return t + run_rules(d, ranges, flows);
-                }
-                ((l, u), Ordering::Less) if l < r.val => {
-                    ranges[ch_to_idx(r.ch)] = [l, r.val];
-                    t += run_rules(d, ranges, flows);
-                    ranges[ch_to_idx(r.ch)] = [r.val, u];
-                }
-                ((l, u), Ordering::Greater) if u >= r.val => {
-                    ranges[ch_to_idx(r.ch)] = [r.val + 1, u];
-                    t += run_rules(d, ranges, flows);
-                    ranges[ch_to_idx(r.ch)] = [l, r.val + 1];
-                }
-                _ => (),
+                println!("{}: {} -> {}", p, trace.join(" -> "), verdict);
             }
         }
 
-        return t + run_rules(&flow.end, ranges, flows);
-    }
-    0
-}
-
-fn solution(input: &str) -> u64 {
-    let lines: Vec<_> = input.lines().collect();
-    let mut ls: Vec<&str> = Vec::new();
-    let mut flows: HashMap<String, Workflow> = HashMap::new();
+        // custom bounds replace part 2, filling in what wasn't given from the puzzle's
+        if min.is_some() || max.is_some() {
+            let min = min.copied().unwrap_or(BOUNDS[0].0);
+            let max = max.copied().unwrap_or(BOUNDS[0].1.max(min));
+
+            if min > max {
+                command
+                    .error(
+                        ErrorKind::ValueValidation,
+                        format!("--max must be at least --min, got {}..{}", min, max),
+                    )
+                    .exit();
+            }
 
-    for line in &lines {
-        if line.is_empty() {
-            flows = parse_flows(&ls);
-            break;
+            println!("combinations: {}", flows.count_accepted([(min, max); 4]));
         }
 
-        ls.push(line);
+        return Ok(());
     }
 
-    run_rules("in", [[1, 4001]; 4], &flows)
+    cli::print_parts(part, &input, part1, part2);
+
+    Ok(())
 }
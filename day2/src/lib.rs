@@ -0,0 +1,94 @@
+use common::error::{parse_num, split_once};
+use common::{ParseError, ParseResult};
+use std::collections::HashMap;
+
+/// (red, green, blue) cubes in the bag for part 1
+const BAG: [(&str, u32); 3] = [("red", 12), ("green", 13), ("blue", 14)];
+
+fn parse_pull<'a>(row: usize, line: &'a str, pull: &'a str) -> ParseResult<Vec<(&'a str, u32)>> {
+    let mut cubes = Vec::new();
+
+    for c in pull.split(',').map(|s| s.trim()) {
+        let (n, colour) = c
+            .split_once(' ')
+            .ok_or_else(|| ParseError::new(row, line, c, "\"<count> <colour>\""))?;
+
+        if !BAG.iter().any(|(b, _)| *b == colour) {
+            return Err(ParseError::new(row, line, colour, "red, green or blue"));
+        }
+
+        cubes.push((colour, parse_num(row, line, n)?));
+    }
+
+    Ok(cubes)
+}
+
+/// Returns the game id and the most cubes of each colour seen in a single pull
+fn parse_game(row: usize, game: &str) -> ParseResult<(u32, HashMap<&str, u32>)> {
+    let mut map: HashMap<&str, u32> = HashMap::new();
+    let (id, data) = split_once(row, game, ":")?;
+    let id = id
+        .strip_prefix("Game ")
+        .ok_or_else(|| ParseError::new(row, game, id, "\"Game <id>\""))?;
+    let id = parse_num(row, game, id)?;
+    let pulls: Vec<&str> = data.trim().split(';').collect();
+
+    for p in pulls {
+        for c in parse_pull(row, game, p)? {
+            let e = *map.entry(c.0).or_insert(c.1);
+            if e < c.1 {
+                map.insert(c.0, c.1);
+            }
+        }
+    }
+
+    Ok((id, map))
+}
+
+pub fn part1(input: &str) -> ParseResult<u32> {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut sum = 0;
+
+    for (row, g) in lines.iter().enumerate() {
+        let (id, map) = parse_game(row, g)?;
+
+        if BAG
+            .iter()
+            .all(|(colour, n)| map.get(colour).unwrap_or(&0) <= n)
+        {
+            sum += id;
+        }
+    }
+
+    Ok(sum)
+}
+
+pub fn part2(input: &str) -> ParseResult<u32> {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut sum = 0;
+
+    for (row, g) in lines.iter().enumerate() {
+        let (_, map) = parse_game(row, g)?;
+
+        sum += map.into_values().reduce(|acc, e| acc * e).unwrap_or(0);
+    }
+
+    Ok(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day02/example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE), Ok(8));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), Ok(2286));
+    }
+}
@@ -0,0 +1,361 @@
+use common::error::split_once;
+use common::{ParseError, ParseResult};
+use gcd::Gcd;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+enum ModType {
+    FlipFlop,
+    Conjunction,
+    /// Button/Output are modeled by broadcast type
+    Broadcast,
+}
+
+#[derive(Debug, Clone)]
+struct Module {
+    id: usize,
+    name: String,
+    mtype: ModType,
+
+    state: bool,
+    conj_state: HashMap<usize, bool>,
+
+    pulses: Vec<(usize, bool)>,
+
+    incoming: Vec<usize>,
+    outgoing: Vec<usize>,
+}
+
+impl PartialEq for Module {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+            && self.name == other.name
+            && self.mtype == other.mtype
+            && self.state == other.state
+            && self.incoming == other.incoming
+            && self.outgoing == other.outgoing
+    }
+}
+
+impl Eq for Module {}
+
+impl Hash for Module {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.name.hash(state);
+        self.mtype.hash(state);
+        self.state.hash(state);
+        self.incoming.hash(state);
+        self.outgoing.hash(state);
+    }
+}
+
+impl Module {
+    fn new(id: usize, name: &str, mtype: ModType, state: bool) -> Self {
+        Module {
+            id,
+            name: name.to_string(),
+            mtype,
+            state,
+            conj_state: HashMap::new(),
+            pulses: Vec::new(),
+            incoming: Vec::new(),
+            outgoing: Vec::new(),
+        }
+    }
+
+    fn send_signal(&self, pulse: bool) -> Option<VecDeque<((usize, usize), bool)>> {
+        let mut to_process = VecDeque::new();
+
+        for id in &self.outgoing {
+            to_process.push_back(((self.id, *id), pulse));
+        }
+
+        Some(to_process)
+    }
+
+    fn process_signal(
+        id: usize,
+        modules: &mut [Module],
+        iter: usize,
+        watch: &mut HashMap<usize, usize>,
+    ) -> Option<VecDeque<((usize, usize), bool)>> {
+        if let Some(module) = modules.get_mut(id) {
+            if let Some((idx, pulse)) = module.pulses.pop() {
+                match module.mtype {
+                    ModType::FlipFlop => {
+                        if !pulse {
+                            module.state = !module.state;
+                            module.send_signal(module.state)
+                        } else {
+                            None
+                        }
+                    }
+                    ModType::Conjunction => {
+                        module.conj_state.entry(idx).and_modify(|v| *v = pulse);
+                        let signal = !module.conj_state.values().all(|b| *b);
+
+                        if signal {
+                            watch.entry(module.id).and_modify(|v| {
+                                if *v == 0 {
+                                    *v = iter
+                                }
+                            });
+                        }
+
+                        module.send_signal(!module.conj_state.values().all(|b| *b))
+                    }
+                    ModType::Broadcast => module.send_signal(pulse),
+                }
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// Presses the button once and returns the number of (low, high) pulses that were sent
+/// Presses the button once and returns the number of (low, high) pulses that were sent
+fn run_module_loop(
+    modules: &mut [Module],
+    iter: usize,
+    watch: &mut HashMap<usize, usize>,
+) -> (u64, u64) {
+    let mut pulses = (0, 0);
+    let mut to_process = VecDeque::new();
+    for s in modules[0].send_signal(false).unwrap() {
+        to_process.push_back(s);
+    }
+
+    while let Some(((in_id, out_id), pulse)) = to_process.pop_front() {
+        if in_id == out_id {
+            for s in Module::process_signal(in_id, modules, iter, watch)
+                .iter()
+                .flatten()
+            {
+                to_process.push_front(*s);
+            }
+
+            continue;
+        }
+
+        if pulse {
+            pulses.1 += 1;
+        } else {
+            pulses.0 += 1;
+        }
+
+        let module = &mut modules[out_id];
+        module.pulses.push((in_id, pulse));
+
+        for s in Module::process_signal(out_id, modules, iter, watch)
+            .iter()
+            .flatten()
+        {
+            to_process.push_back(*s);
+        }
+
+        if !modules[in_id].pulses.is_empty() {
+            to_process.push_front(((in_id, out_id), pulse));
+        }
+    }
+
+    pulses
+}
+
+/// Returns an error at the end of the last line of @input for something it is missing
+fn missing_at_end(input: &str, expected: &str) -> ParseError {
+    let (row, line) = input.lines().enumerate().last().unwrap_or((0, ""));
+
+    ParseError::missing(row, line, expected)
+}
+
+fn parse_modules(input: &str) -> ParseResult<Vec<Module>> {
+    let lines: Vec<_> = input.lines().collect();
+    let mut modules = Vec::new();
+    let mut cons: Vec<(usize, Vec<&str>)> = Vec::new();
+
+    let mut broadcaster_id = None;
+    for (id, line) in (1..).zip(lines) {
+        let row = id - 1;
+        let (name, out) = split_once(row, line, "->")?;
+
+        let mtype = match name.trim() {
+            "broadcaster" => ModType::Broadcast,
+            _ => match name.chars().next() {
+                Some('%') if name.trim().len() > 1 => ModType::FlipFlop,
+                Some('&') if name.trim().len() > 1 => ModType::Conjunction,
+                _ => {
+                    return Err(ParseError::new(
+                        row,
+                        line,
+                        name.trim(),
+                        "broadcaster, %<name> or &<name>",
+                    ))
+                }
+            },
+        };
+
+        if mtype == ModType::Broadcast {
+            broadcaster_id = Some(id);
+            modules.push(Module::new(id, name.trim(), mtype, false));
+        } else {
+            modules.push(Module::new(
+                id,
+                name.chars().skip(1).collect::<String>().trim(),
+                mtype,
+                false,
+            ));
+        }
+
+        cons.push((id, out.split(',').map(|s| s.trim()).collect()));
+    }
+
+    let broadcaster_id = broadcaster_id.ok_or_else(|| missing_at_end(input, "a broadcaster"))?;
+
+    modules.insert(0, Module::new(0, "button", ModType::Broadcast, false));
+    modules[0].outgoing.push(broadcaster_id);
+
+    for (id, out) in cons {
+        for o in out.iter().map(|s| s.trim()) {
+            let mod_id;
+            if let Some(m) = modules.iter_mut().find(|m| m.name.as_str() == o) {
+                if m.mtype == ModType::Conjunction {
+                    m.conj_state.insert(id, false);
+                }
+
+                m.incoming.push(id);
+                mod_id = Some(m.id);
+            } else {
+                modules.insert(
+                    modules.len(),
+                    Module::new(modules.len(), o, ModType::Broadcast, false),
+                );
+
+                let len = modules.len() - 1;
+                modules[len].incoming.push(id);
+                mod_id = Some(len);
+            }
+
+            if let Some(m_id) = mod_id {
+                modules[id].outgoing.push(m_id);
+            }
+        }
+    }
+
+    Ok(modules)
+}
+
+pub fn part1(input: &str) -> ParseResult<u64> {
+    let mut modules = parse_modules(input)?;
+    let mut watch = HashMap::new();
+    let (mut low, mut high) = (0, 0);
+
+    for i in 1..=1000 {
+        let (l, h) = run_module_loop(&mut modules, i, &mut watch);
+        low += l;
+        high += h;
+    }
+
+    Ok(low * high)
+}
+
+/// The flip-flop states and conjunction memories of every module
+fn module_state(modules: &[Module]) -> Vec<bool> {
+    let mut state = Vec::new();
+
+    for m in modules {
+        let mut memory: Vec<(usize, bool)> = m.conj_state.iter().map(|(k, v)| (*k, *v)).collect();
+        memory.sort_unstable();
+
+        state.push(m.state);
+        state.extend(memory.into_iter().map(|(_, v)| v));
+    }
+
+    state
+}
+
+/// Most button presses to wait for every module feeding rx's feeder to send a high
+/// pulse, far more than the puzzle's counters take
+const PRESS_LIMIT: usize = 1 << 16;
+
+pub fn part2(input: &str) -> ParseResult<u64> {
+    let mut modules = parse_modules(input)?;
+    let rx_parent = match modules.iter().find(|m| m.name.as_str() == "rx") {
+        Some(rx) if rx.incoming.len() == 1 => rx.incoming[0],
+        _ => return Err(missing_at_end(input, "a single module that sends to rx")),
+    };
+    let mut to_watch = HashMap::new();
+
+    if modules[rx_parent].mtype != ModType::Conjunction || modules[rx_parent].incoming.is_empty() {
+        return Err(missing_at_end(
+            input,
+            "a conjunction with inputs that sends to rx",
+        ));
+    }
+
+    for mod_id in modules[rx_parent].incoming.clone() {
+        to_watch.insert(mod_id, 0);
+    }
+
+    // once the modules are back in a state they were in, they go round the same
+    // presses forever
+    let mut seen = HashSet::from([module_state(&modules)]);
+    let mut iterations = 1;
+    loop {
+        run_module_loop(&mut modules, iterations, &mut to_watch);
+        iterations += 1;
+
+        if to_watch.values().all(|v| *v != 0) {
+            break;
+        } else if iterations > PRESS_LIMIT || !seen.insert(module_state(&modules)) {
+            return Err(missing_at_end(
+                input,
+                "modules feeding rx's feeder that all send a high pulse at some point",
+            ));
+        }
+    }
+
+    let lcm = |a: usize, b: usize| a * (b / a.gcd(b));
+    Ok(to_watch.into_values().reduce(lcm).unwrap_or(0) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE1: &str = include_str!("../../inputs/day20/example1.txt");
+    const EXAMPLE2: &str = include_str!("../../inputs/day20/example2.txt");
+
+    #[test]
+    fn part1_example1() {
+        assert_eq!(part1(EXAMPLE1), Ok(32000000));
+    }
+
+    #[test]
+    fn part1_example2() {
+        assert_eq!(part1(EXAMPLE2), Ok(11687500));
+    }
+
+    #[test]
+    fn rx_counters() {
+        // a goes high every 2nd press and b every 4th, like two small counters
+        let input = "broadcaster -> p, q\n%p -> a\n%q -> r\n%r -> b\n&a -> f\n&b -> f\n&f -> rx\n";
+
+        assert_eq!(part2(input), Ok(4));
+    }
+
+    #[test]
+    fn rx_never_low() {
+        // y only ever hears high pulses from x, so it never sends one to f
+        let e = part2("broadcaster -> x\n&x -> y\n&y -> f\n&f -> rx\n").unwrap_err();
+        assert_eq!((e.line, e.column), (4, 9));
+
+        // nothing sends to f
+        let e = part2("broadcaster -> a\n%a -> b\n&f -> rx\n").unwrap_err();
+        assert_eq!(e.expected, "a conjunction with inputs that sends to rx");
+    }
+}
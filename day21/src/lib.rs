@@ -0,0 +1,188 @@
+use common::{ParseError, ParseResult};
+use grid::Grid;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Plot,
+    Rock,
+}
+
+impl Tile {
+    fn is_traversable(&self) -> bool {
+        *self != Tile::Rock
+    }
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Tile::Plot => write!(f, "."),
+            Tile::Rock => write!(f, "#"),
+        }
+    }
+}
+
+/// Prints @grid with the plots reached in exactly @step_limit steps from @start as 'O'
+#[allow(unused)]
+fn print_grid(start: (usize, usize), step_limit: usize, grid: &Grid<Tile>) {
+    let reached = reachable(start, step_limit, grid);
+    let mut chars = grid.map(|t| t.to_string().chars().next().unwrap());
+
+    for pos in reached {
+        chars[pos] = 'O';
+    }
+    chars[start] = 'S';
+
+    print!("{}", chars);
+}
+
+fn get_adjacent(pos: (usize, usize), grid: &Grid<Tile>) -> Vec<(usize, usize)> {
+    grid.neighbours4(pos)
+        .filter(|p| grid[*p].is_traversable())
+        .collect()
+}
+
+/// Plots that can be reached in exactly @limit steps from @start. Walking back and
+/// forth, that is every plot at most @limit steps away an even number of steps short.
+fn reachable(start: (usize, usize), limit: usize, grid: &Grid<Tile>) -> Vec<(usize, usize)> {
+    search::bfs_reach([start], |p| get_adjacent(*p, grid), Some(limit))
+        .into_iter()
+        .filter(|(_, steps)| (limit - steps).is_multiple_of(2))
+        .map(|(p, _)| p)
+        .collect()
+}
+
+/// https://en.wikipedia.org/wiki/Divided_differences
+fn div_diff(points: &[(f64, f64)], k: usize) -> f64 {
+    if k == 0 {
+        return points[0].1;
+    }
+
+    let k_0 = (points[k].1 - points[k - 1].1) / (points[k].0 - points[k - 1].0);
+
+    if k == 1 {
+        return k_0;
+    }
+
+    (points[k].1 - points[k - 1].1 - div_diff(points, k - 1)) / (points[k].0 - points[0].0)
+}
+
+/// https://en.wikipedia.org/wiki/Newton_polynomial
+fn poly_fit(points: &[(f64, f64)], x: f64) -> f64 {
+    let poly = |x: f64| {
+        div_diff(points, 0)
+            + div_diff(points, 1) * (x - points[0].0)
+            + div_diff(points, 2) * (x - points[0].0) * (x - points[1].0)
+    };
+
+    poly(x)
+}
+
+/// Returns the grid and the starting position
+fn parse_grid(input: &str) -> ParseResult<(Grid<Tile>, (usize, usize))> {
+    let chars = Grid::parse(input, "\".\", \"#\" or \"S\"", |c| {
+        "#.S".contains(c).then_some(c)
+    })?;
+    let mut starts = chars.iter().filter(|(_, c)| **c == 'S').map(|(p, _)| p);
+
+    let start = starts.next().ok_or_else(|| {
+        let (row, line) = input.lines().enumerate().last().unwrap_or((0, ""));
+
+        ParseError::missing(row, line, "a start \"S\"")
+    })?;
+
+    if let Some((r, c)) = starts.next() {
+        let line = input.lines().nth(r).unwrap();
+
+        return Err(ParseError::new(r, line, &line[c..=c], "only one start"));
+    }
+
+    let grid = chars.map(|c| match c {
+        '#' => Tile::Rock,
+        _ => Tile::Plot,
+    });
+
+    Ok((grid, start))
+}
+
+/// Number of plots that can be reached in exactly @step_limit steps
+pub fn part1(input: &str, step_limit: usize) -> ParseResult<u64> {
+    let (grid, start) = parse_grid(input)?;
+
+    Ok(reachable(start, step_limit, &grid).len() as u64)
+}
+
+/// https://www.reddit.com/r/adventofcode/comments/18nevo3/comment/keaiiq7/?utm_source=share&utm_medium=web3x&utm_name=web3xcss&utm_term=1&utm_content=share_button
+pub fn part2(input: &str, step_limit: usize) -> ParseResult<u64> {
+    let (grid, start) = parse_grid(input)?;
+
+    let grid_limit = grid.height();
+    if grid.height() != grid.width() {
+        let (row, line) = input.lines().enumerate().last().unwrap();
+
+        return Err(ParseError::missing(
+            row,
+            line,
+            format!("{} rows", grid.width()),
+        ));
+    }
+
+    // the fit only works when the steps left after crossing whole gardens reach from
+    // the middle to the edge
+    let remainder = step_limit % grid_limit;
+    if remainder != grid_limit / 2 {
+        let (row, line) = input.lines().enumerate().last().unwrap();
+
+        return Err(ParseError::missing(
+            row,
+            line,
+            format!("a garden of width w with {} % w == w / 2", step_limit),
+        ));
+    }
+
+    let mut fn_vals = [0, 0, 0];
+
+    for (i, v) in fn_vals.iter_mut().enumerate() {
+        // 1, 3 and 5 copies of the grid across
+        let copies = if i == 0 {
+            1
+        } else {
+            u64::pow(2, i as u32) as usize + 1
+        };
+        let fn_grid = grid.tiled(copies, copies);
+
+        let search = grid_limit * i;
+        let start = (start.0 + search, start.1 + search);
+        *v = reachable(start, remainder + search, &fn_grid).len() as u64;
+    }
+
+    let fn_vals: Vec<_> = fn_vals
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (i as f64, *v as f64))
+        .collect();
+
+    Ok(poly_fit(&fn_vals, (step_limit / grid_limit) as f64) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day21/example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE, 6), Ok(16));
+    }
+
+    #[test]
+    fn other_sizes() {
+        // with no rocks every plot of the right parity within reach counts
+        assert_eq!(part2("...\n.S.\n...\n", 26501365), Ok(26501366 * 26501366));
+
+        let e = part2("....\n.S..\n....\n....\n", 26501365).unwrap_err();
+        assert_eq!((e.line, e.column), (4, 5));
+    }
+}
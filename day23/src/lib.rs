@@ -0,0 +1,267 @@
+use common::{ParseError, ParseResult};
+use grid::{Direction, Grid};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    hash::Hash,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+enum TileType {
+    Path,
+    Forest,
+    Slope(Direction),
+}
+
+impl TileType {
+    /// Whether this tile can be entered moving in @direction. Slopes can only be
+    /// entered downhill if they are @slippery.
+    fn is_passable(&self, direction: Direction, slippery: bool) -> bool {
+        use TileType::*;
+
+        match self {
+            Path => true,
+            Forest => false,
+            Slope(d) => !slippery || *d == direction,
+        }
+    }
+}
+
+impl Display for TileType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use TileType::*;
+
+        write!(
+            f,
+            "{}",
+            match self {
+                Path => '.',
+                Forest => '#',
+                Slope(d) => d.arrow(),
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    idx: usize,
+    pos: (usize, usize),
+    edges: Vec<(usize, i64)>,
+}
+
+impl Node {
+    fn new(pos: (usize, usize)) -> Self {
+        Node {
+            idx: 0,
+            pos,
+            edges: Vec::new(),
+        }
+    }
+
+    fn add_edge(&mut self, node: usize, weight: i64) {
+        self.edges.push((node, weight));
+    }
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.pos == other.pos
+    }
+}
+
+impl Eq for Node {}
+
+impl Hash for Node {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.pos.hash(state);
+    }
+}
+
+fn get_adjacent(
+    current: (usize, usize),
+    graph: &Grid<TileType>,
+    slippery: bool,
+) -> Vec<(usize, usize)> {
+    Direction::ALL
+        .into_iter()
+        .filter_map(|dir| {
+            graph
+                .step(current, dir)
+                .filter(|p| graph[*p].is_passable(dir, slippery))
+        })
+        .collect()
+}
+
+/// Junctions are found ignoring slopes, so they are the same in both parts
+fn is_junction(pos: (usize, usize), tiles: &Grid<TileType>) -> bool {
+    tiles[pos] != TileType::Forest && get_adjacent(pos, tiles, false).len() > 2
+}
+
+fn create_nodes(start: (usize, usize), end: (usize, usize), tiles: &Grid<TileType>) -> Vec<Node> {
+    let mut nodes = Vec::new();
+
+    let mut idx = 0;
+    nodes.push(Node::new(start));
+    nodes[0].idx = idx;
+
+    for i in 1..tiles.height() {
+        for j in 1..(tiles.width() - 1) {
+            if is_junction((i, j), tiles) {
+                nodes.push(Node::new((i, j)));
+                idx += 1;
+                nodes[idx].idx = idx;
+            }
+        }
+    }
+
+    nodes.push(Node::new(end));
+    idx += 1;
+    nodes[idx].idx = idx;
+    nodes
+}
+
+fn find_adj_intersections(
+    inter: (usize, usize),
+    tiles: &Grid<TileType>,
+    slippery: bool,
+) -> Vec<((usize, usize), u64)> {
+    let mut adj_ints = Vec::new();
+    let adj = get_adjacent(inter, tiles, slippery);
+
+    for a in adj {
+        let mut curr = a;
+        let mut weight = 1;
+        let mut seen = HashSet::new();
+
+        seen.insert(inter);
+        loop {
+            let mut next = get_adjacent(curr, tiles, slippery);
+            next.retain(|n| !seen.contains(n));
+
+            seen.insert(curr);
+            weight += 1;
+
+            if next.len() != 1 || is_junction(curr, tiles) {
+                break;
+            }
+
+            curr = next.pop().unwrap();
+        }
+
+        adj_ints.push((curr, weight));
+    }
+
+    adj_ints
+}
+
+fn create_edges(nodes: &mut [Node], tiles: &Grid<TileType>, slippery: bool) {
+    let mut adj_ints = HashMap::new();
+    for n in nodes.iter() {
+        let adj = find_adj_intersections(n.pos, tiles, slippery);
+
+        for a in adj {
+            adj_ints
+                .entry(n.pos)
+                .and_modify(|v: &mut Vec<_>| v.push(a))
+                .or_insert(vec![a]);
+        }
+    }
+
+    for (k, v) in adj_ints {
+        let idx = nodes.iter().find(|n| n.pos == k).unwrap().idx;
+
+        for (n, w) in v {
+            // dead ends (only possible on slippery slopes) don't lead to another node
+            if let Some(enode) = nodes.iter().find(|en| en.pos == n).cloned() {
+                nodes[idx].add_edge(enode.idx, w as i64 - 1);
+            }
+        }
+    }
+}
+
+/*
+ * I got this from another person's solution. I had the right algorithm, but hashing the
+ * Nodes to put into a HashSet and finding the node based on position ever loop
+ * caused it to take forever (i.e. I never tried to even let it finish). This uses bit
+ * manipulation to mark indexes as seen, and it goes pretty quick!
+ */
+fn find_longest_path(start: usize, end: usize, nodes: &[Node]) -> u64 {
+    let mut max = 0;
+    let mut stack = Vec::new();
+
+    stack.push((start, 0, 0i64));
+
+    while let Some((node, steps, vis)) = stack.pop() {
+        if node == end {
+            max = max.max(steps);
+            continue;
+        }
+
+        for e in &nodes[node].edges {
+            if vis & (1 << e.0) == 0 {
+                stack.push((nodes[e.0].idx, steps + e.1, vis | (1 << e.0)));
+            }
+        }
+    }
+
+    max as u64
+}
+
+fn longest_hike(input: &str, slippery: bool) -> ParseResult<u64> {
+    let graph = Grid::parse(input, "a path, forest or slope", |c| match c {
+        '.' => Some(TileType::Path),
+        '#' => Some(TileType::Forest),
+        '^' | 'v' | '<' | '>' => Direction::from_char(c).map(TileType::Slope),
+        _ => None,
+    })?;
+
+    if graph.height() < 2 {
+        return Err(ParseError::missing(graph.height(), "", "at least 2 rows"));
+    }
+
+    let last = graph.height() - 1;
+    let find_path = |row: usize| {
+        (0..graph.width())
+            .map(|c| (row, c))
+            .find(|p| graph[*p] == TileType::Path)
+    };
+    let start = find_path(0).ok_or_else(|| {
+        ParseError::missing(0, input.lines().next().unwrap(), "a path to start from")
+    })?;
+    let end = find_path(last).ok_or_else(|| {
+        ParseError::missing(last, input.lines().nth(last).unwrap(), "a path to end at")
+    })?;
+
+    let mut nodes = create_nodes(start, end, &graph);
+    create_edges(&mut nodes, &graph, slippery);
+
+    let start = nodes.iter().find(|n| n.pos == start).unwrap();
+    let end = nodes.iter().find(|n| n.pos == end).unwrap();
+    Ok(find_longest_path(start.idx, end.idx, &nodes))
+}
+
+pub fn part1(input: &str) -> ParseResult<u64> {
+    longest_hike(input, true)
+}
+
+pub fn part2(input: &str) -> ParseResult<u64> {
+    longest_hike(input, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day23/example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE), Ok(94));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), Ok(154));
+    }
+}
@@ -0,0 +1,173 @@
+use common::error::{parse_num, split_once};
+use common::{ParseError, ParseResult};
+use nalgebra::{matrix, vector, Matrix3, Matrix6, Vector3};
+
+#[derive(Debug)]
+struct Hailstone {
+    pos: Vector3<f64>,
+    vel: Vector3<f64>,
+}
+
+impl Hailstone {
+    fn from_vecs(pos: &[f64], vel: &[f64]) -> Option<Self> {
+        if pos.len() != 3 || vel.len() != 3 {
+            return None;
+        }
+
+        Some(Self {
+            pos: vector![pos[0], pos[1], pos[2]],
+            vel: vector![vel[0], vel[1], vel[2]],
+        })
+    }
+}
+
+/// Whether the future paths of @a and @b cross inside the [@min, @max] test area,
+/// only looking at the x and y axes
+fn paths_cross(a: &Hailstone, b: &Hailstone, min: f64, max: f64) -> bool {
+    let det = a.vel.x * b.vel.y - a.vel.y * b.vel.x;
+
+    // parallel paths never cross
+    if det == 0.0 {
+        return false;
+    }
+
+    let (dx, dy) = (b.pos.x - a.pos.x, b.pos.y - a.pos.y);
+    let t = (dx * b.vel.y - dy * b.vel.x) / det;
+    let s = (dx * a.vel.y - dy * a.vel.x) / det;
+
+    if t < 0.0 || s < 0.0 {
+        return false;
+    }
+
+    let (x, y) = (a.pos.x + t * a.vel.x, a.pos.y + t * a.vel.y);
+    (min..=max).contains(&x) && (min..=max).contains(&y)
+}
+
+fn skew_matrix(v: &Vector3<f64>) -> Matrix3<f64> {
+    matrix![0.0, -v[2], v[1];
+            v[2], 0.0, -v[0];
+            -v[1], v[0], 0.0]
+}
+
+/// Sum of the coordinates the rock is thrown from to hit every hailstone, worked out
+/// from the first 3 of @stones. None if they don't pin down a single throw, like when
+/// some of them are parallel.
+fn find_solution(stones: &[Hailstone]) -> Option<u64> {
+    let v0 = -stones[0].pos.cross(&stones[0].vel) + stones[1].pos.cross(&stones[1].vel);
+    let v1 = -stones[0].pos.cross(&stones[0].vel) + stones[2].pos.cross(&stones[2].vel);
+    let rhs = matrix![v0[0]; v0[1]; v0[2]; v1[0]; v1[1]; v1[2]];
+
+    let m00 = skew_matrix(&stones[0].vel) - skew_matrix(&stones[1].vel);
+    let m30 = skew_matrix(&stones[0].vel) - skew_matrix(&stones[2].vel);
+    let m03 = -skew_matrix(&stones[0].pos) + skew_matrix(&stones[1].pos);
+    let m33 = -skew_matrix(&stones[0].pos) + skew_matrix(&stones[2].pos);
+    let m: Matrix6<f64> = matrix![ m00[(0, 0)], m00[(0, 1)], m00[(0, 2)], m03[(0, 0)], m03[(0, 1)], m03[(0, 2)];
+       m00[(1, 0)], m00[(1, 1)], m00[(1, 2)], m03[(1, 0)], m03[(1, 1)], m03[(1, 2)];
+       m00[(2, 0)], m00[(2, 1)], m00[(2, 2)], m03[(2, 0)], m03[(2, 1)], m03[(2, 2)];
+       m30[(0, 0)], m30[(0, 1)], m30[(0, 2)], m33[(0, 0)], m33[(0, 1)], m33[(0, 2)];
+       m30[(1, 0)], m30[(1, 1)], m30[(1, 2)], m33[(1, 0)], m33[(1, 1)], m33[(1, 2)];
+       m30[(2, 0)], m30[(2, 1)], m30[(2, 2)], m33[(2, 0)], m33[(2, 1)], m33[(2, 2)];
+    ];
+
+    let res = m.try_inverse()? * rhs;
+
+    let mut sum = 0;
+    for i in 0..3 {
+        sum += res[(i, 0)].round() as u64;
+    }
+
+    Some(sum)
+}
+
+/// Parses @vec, a slice of the @row'th @line, as a comma separated vector
+fn parse_vec(row: usize, line: &str, vec: &str) -> ParseResult<Vec<f64>> {
+    let v = vec
+        .split(',')
+        .map(|s| parse_num(row, line, s.trim()))
+        .collect::<ParseResult<Vec<f64>>>()?;
+
+    if v.len() != 3 {
+        return Err(ParseError::new(row, line, vec.trim(), "\"<x>, <y>, <z>\""));
+    }
+
+    Ok(v)
+}
+
+fn parse_stones(input: &str) -> ParseResult<Vec<Hailstone>> {
+    let lines: Vec<_> = input.lines().collect();
+    let mut stones = Vec::new();
+
+    for (row, line) in lines.into_iter().enumerate() {
+        let (pos, vel) = split_once(row, line, "@")?;
+
+        let pvec = parse_vec(row, line, pos)?;
+        let vvec = parse_vec(row, line, vel)?;
+
+        stones.push(Hailstone::from_vecs(&pvec, &vvec).unwrap());
+    }
+
+    Ok(stones)
+}
+
+/// Counts the pairs of hailstones whose paths cross inside the [@min, @max] test area
+pub fn part1(input: &str, min: f64, max: f64) -> ParseResult<u64> {
+    let stones = parse_stones(input)?;
+    let mut crossings = 0;
+
+    for i in 0..stones.len() {
+        for j in (i + 1)..stones.len() {
+            if paths_cross(&stones[i], &stones[j], min, max) {
+                crossings += 1;
+            }
+        }
+    }
+
+    Ok(crossings)
+}
+
+pub fn part2(input: &str) -> ParseResult<u64> {
+    let stones = parse_stones(input)?;
+
+    if stones.len() < 3 {
+        let (row, line) = input.lines().enumerate().last().unwrap_or((0, ""));
+
+        return Err(ParseError::missing(row, line, "at least 3 hailstones"));
+    }
+
+    find_solution(&stones).ok_or_else(|| {
+        let line = input.lines().nth(2).unwrap();
+
+        ParseError::new(
+            2,
+            line,
+            line,
+            "first 3 hailstones that pin down a single throw",
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day24/example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE, 7.0, 27.0), Ok(2));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), Ok(47));
+    }
+
+    #[test]
+    fn parallel_stones() {
+        let e = part2("0, 0, 0 @ 1, 1, 1\n0, 0, 0 @ 1, 1, 1\n0, 0, 0 @ 1, 1, 1\n").unwrap_err();
+        assert_eq!((e.line, e.column), (3, 1));
+
+        let e = part2("0, 0, 0 @ 1, 2, 3\n1, 0, 0 @ 1, 2, 3\n0, 1, 0 @ 1, 2, 3\n").unwrap_err();
+        assert_eq!(e.line, 3);
+    }
+}
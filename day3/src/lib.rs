@@ -0,0 +1,136 @@
+use common::error::{grid_lines, parse_num};
+use common::ParseResult;
+
+/// The numbers in @line, the @row'th line, that are next to (or on) the byte at @idx
+fn construct_numbers(row: usize, line: &str, idx: usize) -> ParseResult<Vec<u32>> {
+    let bytes = line.as_bytes();
+    let mut nums = Vec::new();
+
+    let mut j = 0;
+    while j < bytes.len() {
+        if !bytes[j].is_ascii_digit() {
+            j += 1;
+            continue;
+        }
+
+        let start = j;
+        while j < bytes.len() && bytes[j].is_ascii_digit() {
+            j += 1;
+        }
+
+        if start <= idx + 1 && j >= idx {
+            nums.push(parse_num(row, line, &line[start..j])?);
+        }
+    }
+
+    Ok(nums)
+}
+
+/// takes the (row, line) around a * and the idx of the * in lines[1]
+fn find_numbers(lines: &[Option<(usize, &str)>], idx: usize) -> ParseResult<Vec<u32>> {
+    let mut nums = Vec::new();
+
+    for (row, line) in lines.iter().flatten() {
+        nums.append(&mut construct_numbers(*row, line, idx)?);
+    }
+
+    Ok(nums)
+}
+
+fn is_symbol(c: u8) -> bool {
+    !c.is_ascii_digit() && c != b'.'
+}
+
+/// takes the lines around a number and the [start, end) span of the number in lines[1]
+fn is_part_number(lines: &[Option<&str>], start: usize, end: usize) -> bool {
+    let start = start.saturating_sub(1);
+
+    for line in lines.iter().flatten() {
+        let end = (end + 1).min(line.len());
+
+        if line.as_bytes()[start..end].iter().any(|c| is_symbol(*c)) {
+            return true;
+        }
+    }
+
+    false
+}
+
+pub fn part1(input: &str) -> ParseResult<u32> {
+    let lines = grid_lines(input)?;
+    let mut sum = 0;
+
+    for i in 0..lines.len() {
+        let l0 = if i == 0 { None } else { Some(lines[i - 1]) };
+        let l2 = lines.get(i + 1).copied();
+        let bytes = lines[i].as_bytes();
+
+        let mut j = 0;
+        while j < bytes.len() {
+            if !bytes[j].is_ascii_digit() {
+                j += 1;
+                continue;
+            }
+
+            let start = j;
+            while j < bytes.len() && bytes[j].is_ascii_digit() {
+                j += 1;
+            }
+
+            if is_part_number(&[l0, Some(lines[i]), l2], start, j) {
+                sum += parse_num::<u32>(i, lines[i], &lines[i][start..j])?;
+            }
+        }
+    }
+
+    Ok(sum)
+}
+
+pub fn part2(input: &str) -> ParseResult<u32> {
+    let lines = grid_lines(input)?;
+    let mut sum = 0;
+
+    for i in 0..lines.len() {
+        for (j, c) in lines[i].char_indices() {
+            if c == '*' {
+                let l0 = if i == 0 {
+                    None
+                } else {
+                    Some((i - 1, lines[i - 1]))
+                };
+                let l2 = lines.get(i + 1).map(|l| (i + 1, *l));
+
+                let nums = find_numbers(&[l0, Some((i, lines[i])), l2], j)?;
+                if nums.len() == 2 {
+                    sum += nums[0] * nums[1];
+                }
+            }
+        }
+    }
+
+    Ok(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day03/example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE), Ok(4361));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), Ok(467835));
+    }
+
+    #[test]
+    fn overlong_number() {
+        let e = part2("12345678901*2\n").unwrap_err();
+
+        assert_eq!((e.line, e.column, e.token.as_str()), (1, 1, "12345678901"));
+    }
+}
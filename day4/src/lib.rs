@@ -0,0 +1,74 @@
+use common::error::{parse_nums, split_once};
+use common::{ParseError, ParseResult};
+use std::collections::HashSet;
+
+fn get_matching(row: usize, line: &str, winning: &str, have: &str) -> ParseResult<u32> {
+    let wnums: HashSet<u32> = parse_nums(row, line, winning)?.into_iter().collect();
+    let have: Vec<u32> = parse_nums(row, line, have)?;
+
+    Ok(have.iter().filter(|n| wnums.contains(n)).count() as u32)
+}
+
+fn get_num_cards(mut card_instances: Vec<(u32, u32)>) -> u32 {
+    let len = card_instances.len();
+
+    for i in 0..len {
+        let (n, p) = card_instances[i];
+
+        if p > 0 {
+            for j in 0..(p as usize) {
+                let k = (j + 1) % (len - i);
+                let (nk, pk) = card_instances[i + k];
+                card_instances[i + k] = (nk + n, pk);
+            }
+        }
+    }
+
+    card_instances.iter().map(|(i, _)| i).sum()
+}
+
+/// Returns (num card instances, num matching numbers) for each card
+fn parse_cards(input: &str) -> ParseResult<Vec<(u32, u32)>> {
+    // holds (num card instances for card i, num points for card i)
+    let mut card_instances: Vec<(u32, u32)> = Vec::new();
+
+    for (row, line) in input.lines().enumerate() {
+        let (_, numbers) = split_once(row, line, ":")?;
+        let (winning, have) = numbers
+            .split_once('|')
+            .ok_or_else(|| ParseError::missing(row, line, "\"|\""))?;
+
+        card_instances.push((1, get_matching(row, line, winning, have)?));
+    }
+
+    Ok(card_instances)
+}
+
+pub fn part1(input: &str) -> ParseResult<u32> {
+    Ok(parse_cards(input)?
+        .iter()
+        .filter(|(_, p)| *p > 0)
+        .map(|(_, p)| 1 << (p - 1))
+        .sum())
+}
+
+pub fn part2(input: &str) -> ParseResult<u32> {
+    Ok(get_num_cards(parse_cards(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day04/example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE), Ok(13));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), Ok(30));
+    }
+}
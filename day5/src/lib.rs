@@ -0,0 +1,307 @@
+use common::error::parse_nums;
+use common::{ParseError, ParseResult};
+use std::thread;
+
+#[derive(Debug, Clone, Copy)]
+struct MapEntry {
+    source: u64,
+    dest: u64,
+    range: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Map {
+    entries: Vec<MapEntry>,
+}
+
+impl MapEntry {
+    fn new(source: u64, dest: u64, range: u64) -> Self {
+        MapEntry {
+            source,
+            dest,
+            range,
+        }
+    }
+
+    /// One past the last source number
+    fn source_end(&self) -> u64 {
+        self.source + self.range
+    }
+
+    fn get_seed_mapping(&self, seed: u64) -> u64 {
+        if self.source <= seed && seed < (self.source + self.range) {
+            seed - self.source + self.dest
+        } else {
+            seed
+        }
+    }
+}
+
+impl Map {
+    fn new() -> Self {
+        Map {
+            entries: Vec::new(),
+        }
+    }
+
+    fn insert(&mut self, e: MapEntry) {
+        self.entries.push(e);
+    }
+
+    fn get_mapping(&self, seed: u64) -> u64 {
+        for e in &self.entries {
+            let m = e.get_seed_mapping(seed);
+            if m != seed {
+                return m;
+            }
+        }
+
+        seed
+    }
+
+    /// Maps every number in @interval, `[start, end)`, splitting it where it crosses
+    /// the bounds of an entry. Numbers no entry covers map to themselves.
+    fn map_interval(&self, interval: (u64, u64)) -> Vec<(u64, u64)> {
+        let mut unmapped = vec![interval];
+        let mut mapped = Vec::new();
+
+        for e in &self.entries {
+            let mut rest = Vec::new();
+
+            for (start, end) in unmapped {
+                let (lo, hi) = (start.max(e.source), end.min(e.source_end()));
+
+                if lo >= hi {
+                    rest.push((start, end));
+                    continue;
+                }
+
+                mapped.push((lo - e.source + e.dest, hi - e.source + e.dest));
+
+                if start < lo {
+                    rest.push((start, lo));
+                }
+
+                if hi < end {
+                    rest.push((hi, end));
+                }
+            }
+
+            unmapped = rest;
+        }
+
+        mapped.append(&mut unmapped);
+        mapped
+    }
+}
+
+fn parse_line(row: usize, line: &str) -> ParseResult<MapEntry> {
+    let nums: Vec<u64> = parse_nums(row, line, line)?;
+
+    if nums.len() < 3 {
+        return Err(ParseError::missing(row, line, "3 numbers"));
+    } else if nums.len() > 3 {
+        let extra = line.split_whitespace().nth(3).unwrap();
+
+        return Err(ParseError::new(row, line, extra, "end of line"));
+    }
+
+    let (dest, source, range) = (nums[0], nums[1], nums[2]);
+    if source.max(dest).checked_add(range).is_none() {
+        let len = line.split_whitespace().nth(2).unwrap();
+
+        return Err(ParseError::new(
+            row,
+            line,
+            len,
+            format!("a range length of at most {}", u64::MAX - source.max(dest)),
+        ));
+    }
+
+    Ok(MapEntry::new(source, dest, range))
+}
+
+fn find_location(seed: u64, maps: &[Map]) -> u64 {
+    maps.iter().fold(seed, |id, e| e.get_mapping(id))
+}
+
+/// Parses the seed numbers and the maps in the order they should be applied
+fn parse_almanac(input: &str) -> ParseResult<(Vec<u64>, Vec<Map>)> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .filter(|(_, s)| !s.trim().is_empty());
+    let (row, first) = lines.next().unwrap_or((0, ""));
+    let seeds = first
+        .strip_prefix("seeds:")
+        .ok_or_else(|| ParseError::new(row, first, first, "\"seeds:\""))?;
+    let seeds: Vec<u64> = parse_nums(row, first, seeds)?;
+    let mut maps: Vec<Map> = Vec::new();
+
+    if seeds.is_empty() {
+        return Err(ParseError::missing(row, first, "seed numbers"));
+    }
+
+    let mut map = Map::new();
+    for (row, line) in lines {
+        if line.ends_with("map:") {
+            if !map.entries.is_empty() {
+                maps.push(map);
+                map = Map::new();
+            }
+
+            continue;
+        }
+
+        map.insert(parse_line(row, line)?);
+    }
+
+    if !map.entries.is_empty() {
+        maps.push(map);
+    }
+
+    Ok((seeds, maps))
+}
+
+pub fn part1(input: &str) -> ParseResult<u64> {
+    let (seeds, maps) = parse_almanac(input)?;
+
+    Ok(seeds
+        .iter()
+        .map(|s| find_location(*s, &maps))
+        .min()
+        .unwrap())
+}
+
+/// Splits the seed numbers into (start, length) ranges, checking every range ends
+/// within a u64
+fn seed_ranges(input: &str, seeds: &[u64]) -> ParseResult<Vec<(u64, u64)>> {
+    let (row, line) = input
+        .lines()
+        .enumerate()
+        .find(|(_, l)| l.starts_with("seeds:"))
+        .unwrap();
+
+    if !seeds.len().is_multiple_of(2) {
+        return Err(ParseError::missing(row, line, "a range length"));
+    }
+
+    for (i, r) in seeds.chunks(2).enumerate() {
+        if r[0].checked_add(r[1]).is_none() {
+            let len = line["seeds:".len()..]
+                .split_whitespace()
+                .nth(2 * i + 1)
+                .unwrap();
+
+            return Err(ParseError::new(
+                row,
+                line,
+                len,
+                format!("a range length of at most {}", u64::MAX - r[0]),
+            ));
+        }
+    }
+
+    Ok(seeds.chunks(2).map(|r| (r[0], r[1])).collect())
+}
+
+/// Maps the seed ranges through every map as whole intervals
+pub fn part2(input: &str) -> ParseResult<u64> {
+    let (seeds, maps) = parse_almanac(input)?;
+
+    let mut intervals: Vec<(u64, u64)> = seed_ranges(input, &seeds)?
+        .into_iter()
+        .filter(|(_, range)| *range > 0)
+        .map(|(start, range)| (start, start + range))
+        .collect();
+
+    for m in &maps {
+        intervals = intervals
+            .into_iter()
+            .flat_map(|i| m.map_interval(i))
+            .collect();
+    }
+
+    Ok(intervals
+        .iter()
+        .map(|(start, _)| *start)
+        .min()
+        .unwrap_or(u64::MAX))
+}
+
+/// Finds the location of every single seed, one thread per seed range. Takes minutes on
+/// a real input, but doesn't share any code with `part2` so it can check it.
+pub fn part2_brute_force(input: &str) -> ParseResult<u64> {
+    let (seeds, maps) = parse_almanac(input)?;
+
+    let mut handles = Vec::new();
+    for (start, range) in seed_ranges(input, &seeds)? {
+        let m = maps.clone();
+        let h = thread::spawn(move || {
+            let mut min = u64::MAX;
+
+            for i in 0..range {
+                min = min.min(find_location(start + i, &m));
+            }
+
+            min
+        });
+
+        handles.push(h);
+    }
+
+    let mut locations = Vec::new();
+    for h in handles {
+        locations.push(h.join().unwrap());
+    }
+
+    Ok(*locations.iter().min().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day05/example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE), Ok(35));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), Ok(46));
+        assert_eq!(part2_brute_force(EXAMPLE), Ok(46));
+    }
+
+    #[test]
+    fn overflowing_range() {
+        let e = part2("seeds: 1 2 18446744073709551610 6\n").unwrap_err();
+
+        assert_eq!((e.line, e.column, e.token.as_str()), (1, 33, "6"));
+        assert_eq!(part2("seeds: 1 2 18446744073709551610 5\n"), Ok(1));
+    }
+
+    #[test]
+    fn overflowing_entry() {
+        let e = part2("seeds: 1 2\n\na-to-b map:\n0 18446744073709551610 10\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.token.as_str()), (4, 24, "10"));
+
+        let e = part1("seeds: 1 2\n\na-to-b map:\n18446744073709551610 0 6\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.token.as_str()), (4, 24, "6"));
+    }
+
+    #[test]
+    fn interval_split() {
+        let mut map = Map::new();
+        map.insert(MapEntry::new(10, 100, 5));
+        map.insert(MapEntry::new(20, 0, 5));
+
+        assert_eq!(
+            map.map_interval((8, 22)),
+            [(100, 105), (0, 2), (8, 10), (15, 20)]
+        );
+        assert_eq!(map.map_interval((30, 40)), [(30, 40)]);
+    }
+}
@@ -1,137 +1,46 @@
-use std::thread;
-use std::{fs, io};
+use clap::{arg, command, ArgAction};
+use common::cli::{self, Part};
+use day5::{part1, part2, part2_brute_force};
+use std::io;
+use std::process;
 
-#[derive(Debug, Clone, Copy)]
-struct MapEntry {
-    source: u64,
-    dest: u64,
-    range: u64,
-}
+fn main() -> Result<(), io::Error> {
+    let matches = command!()
+        .args(cli::input_args())
+        .arg(cli::part_arg())
+        .arg(
+            arg!(brute_force: --"brute-force" "Check part 2 against trying every single seed (slow)")
+                .action(ArgAction::SetTrue),
+        )
+        .get_matches();
 
-#[derive(Debug, Clone)]
-struct Map {
-    entries: Vec<MapEntry>,
-}
+    let input = cli::read_input(5, &matches)?;
+    let part = *matches.get_one::<Part>("part").unwrap();
 
-impl MapEntry {
-    fn new(source: u64, dest: u64, range: u64) -> Self {
-        MapEntry {
-            source,
-            dest,
-            range,
-        }
+    if !matches.get_flag("brute_force") {
+        cli::print_parts(part, &input, part1, part2);
+        return Ok(());
     }
 
-    fn get_seed_mapping(&self, seed: u64) -> u64 {
-        if self.source <= seed && seed < (self.source + self.range) {
-            seed - self.source + self.dest
-        } else {
-            seed
-        }
+    if part.runs_part1() {
+        cli::print_answer(1, &input, part1(&input.text));
     }
-}
 
-impl Map {
-    fn new() -> Self {
-        Map {
-            entries: Vec::new(),
-        }
-    }
+    if part.runs_part2() {
+        let brute = part2_brute_force(&input.text);
+        let intervals = part2(&input.text);
+        let mismatch = match (&brute, &intervals) {
+            (Ok(b), Ok(i)) if b != i => Some(*i),
+            _ => None,
+        };
 
-    fn insert(&mut self, e: MapEntry) {
-        self.entries.push(e);
-    }
+        cli::print_answer(2, &input, brute);
 
-    fn get_mapping(&self, seed: u64) -> u64 {
-        for e in &self.entries {
-            let m = e.get_seed_mapping(seed);
-            if m != seed {
-                return m;
-            }
+        if let Some(i) = mismatch {
+            eprintln!("error: the interval mapping gives {}", i);
+            process::exit(1);
         }
-
-        seed
     }
-}
-
-fn main() -> Result<(), io::Error> {
-    let input = fs::read_to_string("../input.txt")?;
 
-    println!("solution: {}", solution(&input));
     Ok(())
 }
-
-fn parse_line(line: &str) -> MapEntry {
-    let nums: Vec<u64> = line
-        .split_whitespace()
-        .map(|s| s.parse::<u64>().unwrap())
-        .collect();
-
-    if nums.len() != 3 {
-        println!("line: {}", line);
-        println!("nums: {:?}", nums);
-        panic!("Bad input!");
-    }
-
-    MapEntry::new(nums[1], nums[0], nums[2])
-}
-
-fn find_location(seed: u64, maps: &[Map]) -> u64 {
-    maps.iter().fold(seed, |id, e| e.get_mapping(id))
-}
-
-fn solution(input: &str) -> u64 {
-    let lines: Vec<&str> = input.lines().filter(|s| !s.trim().is_empty()).collect();
-    let seeds: Vec<u64> = lines[0]
-        .split_whitespace()
-        .skip(1)
-        .map(|s| s.parse::<u64>().unwrap())
-        .collect();
-    let mut maps: Vec<Map> = Vec::new();
-
-    let mut map = Map::new();
-    let mut iter = lines.iter().skip(1).peekable();
-    while iter.peek().is_some() {
-        let line = iter.next().unwrap();
-
-        if line.ends_with("map:") {
-            if !map.entries.is_empty() {
-                maps.push(map);
-                map = Map::new();
-            }
-
-            continue;
-        }
-
-        map.insert(parse_line(line));
-    }
-
-    if !map.entries.is_empty() {
-        maps.push(map);
-    }
-
-    let mut handles = Vec::new();
-    let ranges = seeds.chunks(2);
-    for r in ranges {
-        let (start, range) = (r[0], r[1]);
-        let m = maps.clone();
-        let h = thread::spawn(move || {
-            let mut min = u64::MAX;
-
-            for i in 0..range {
-                min = min.min(find_location(start + i, &m));
-            }
-
-            min
-        });
-
-        handles.push(h);
-    }
-
-    let mut locations = Vec::new();
-    for h in handles {
-        locations.push(h.join().unwrap());
-    }
-
-    *locations.iter().min().unwrap()
-}
@@ -0,0 +1,202 @@
+use common::error::{parse_nums, split_once};
+use common::{ParseError, ParseResult};
+use num_bigint::BigUint;
+
+/*
+ * Holding the button for i ms of a race lasting t ms goes i * (t - i) mm, which beats
+ * the record d when i^2 - t*i + d < 0. Writing k = 2i - t that is k^2 < t^2 - 4d, so
+ * the winning i are the k of the same parity as t with |k| < sqrt(t^2 - 4d). If m is
+ * the largest such k, they are -m, -m + 2, ..., m: m + 1 ways.
+ */
+
+/// Number of ways to beat the record distance @d in a race lasting @t
+fn count_ways(t: u64, d: u64) -> u64 {
+    let (t, d) = (t as u128, d as u128);
+
+    // t^2 fits in a u128, so the discriminant can't overflow
+    let disc = match (t * t).checked_sub(4 * d) {
+        Some(disc) if disc > 0 => disc,
+        _ => return 0,
+    };
+
+    let mut m = (disc - 1).isqrt();
+    if m % 2 != t % 2 {
+        if m == 0 {
+            return 0;
+        }
+
+        m -= 1;
+    }
+
+    (m + 1) as u64
+}
+
+/// `count_ways` for races too long for a u64
+fn count_ways_big(t: &BigUint, d: &BigUint) -> BigUint {
+    let four_d = d * 4u32;
+    let t_sq = t * t;
+
+    if t_sq <= four_d {
+        return BigUint::ZERO;
+    }
+
+    let mut m = (t_sq - four_d - 1u32).sqrt();
+    if m.bit(0) != t.bit(0) {
+        if m == BigUint::ZERO {
+            return BigUint::ZERO;
+        }
+
+        m -= 1u32;
+    }
+
+    m + 1u32
+}
+
+/// Number of ways to win a race of any length, through u64s when it fits
+fn race(t: &BigUint, d: &BigUint) -> BigUint {
+    match (u64::try_from(t), u64::try_from(d)) {
+        (Ok(t), Ok(d)) => count_ways(t, d).into(),
+        _ => count_ways_big(t, d),
+    }
+}
+
+fn parse_columns(row: usize, line: &str) -> ParseResult<Vec<BigUint>> {
+    let (_, nums) = split_once(row, line, ":")?;
+
+    parse_nums(row, line, nums)
+}
+
+fn parse_kerned(row: usize, line: &str) -> ParseResult<BigUint> {
+    let (_, nums) = split_once(row, line, ":")?;
+    parse_nums::<BigUint>(row, line, nums)?;
+
+    nums.replace(' ', "")
+        .parse::<BigUint>()
+        .map_err(|_| ParseError::new(row, line, nums.trim(), "a number"))
+}
+
+/// Returns the time and distance lines of the input
+fn race_lines(input: &str) -> ParseResult<(&str, &str)> {
+    let lines: Vec<&str> = input.lines().collect();
+
+    match lines.len() {
+        0 => Err(ParseError::missing(0, "", "\"Time:\"")),
+        1 => Err(ParseError::missing(1, "", "\"Distance:\"")),
+        2 => Ok((lines[0], lines[1])),
+        _ => Err(ParseError::new(2, lines[2], lines[2], "end of input")),
+    }
+}
+
+/// Every column is its own race
+pub fn part1(input: &str) -> ParseResult<BigUint> {
+    let (time_line, distance_line) = race_lines(input)?;
+    let times = parse_columns(0, time_line)?;
+    let distances = parse_columns(1, distance_line)?;
+
+    if distances.len() < times.len() {
+        return Err(ParseError::missing(
+            1,
+            distance_line,
+            "a distance for every time",
+        ));
+    } else if distances.len() > times.len() {
+        let (_, nums) = split_once(1, distance_line, ":")?;
+        let extra = nums.split_whitespace().nth(times.len()).unwrap();
+
+        return Err(ParseError::new(
+            1,
+            distance_line,
+            extra,
+            "a time for every distance",
+        ));
+    }
+
+    Ok(times
+        .iter()
+        .zip(distances.iter())
+        .map(|(t, d)| race(t, d))
+        .product())
+}
+
+/// The spaces between the digits are bad kerning, so there is one long race
+pub fn part2(input: &str) -> ParseResult<BigUint> {
+    let (time_line, distance_line) = race_lines(input)?;
+    let time = parse_kerned(0, time_line)?;
+    let distance = parse_kerned(1, distance_line)?;
+
+    Ok(race(&time, &distance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day06/example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE), Ok(288u32.into()));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), Ok(71503u32.into()));
+    }
+
+    #[test]
+    fn mismatched_rows() {
+        let e = part1("Time: 7 15\nDistance: 9 40 200\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.token.as_str()), (2, 16, "200"));
+
+        let e = part1("Time: 7 15 30\nDistance: 9 40\n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 15));
+    }
+
+    /// Counts the ways by trying every hold time
+    fn brute_force(t: u64, d: u64) -> u64 {
+        (0..=t).filter(|i| i * (t - i) > d).count() as u64
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for t in 0..60 {
+            for d in 0..t * t / 4 + 3 {
+                assert_eq!(count_ways(t, d), brute_force(t, d), "t = {t}, d = {d}");
+                assert_eq!(
+                    count_ways_big(&t.into(), &d.into()),
+                    brute_force(t, d).into(),
+                    "t = {t}, d = {d}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn exact_roots() {
+        // the roots are 2 and 8 exactly, and tying the record doesn't win
+        assert_eq!(count_ways(10, 16), 5);
+        // a single root at t / 2
+        assert_eq!(count_ways(10, 25), 0);
+        assert_eq!(count_ways(10, 24), 1);
+        // the record is 0 so everything but not moving wins
+        assert_eq!(count_ways(10, 0), 9);
+        assert_eq!(count_ways(0, 0), 0);
+    }
+
+    #[test]
+    fn beyond_u64() {
+        let t = BigUint::from(u64::MAX) * 1000u32;
+
+        // one ms short of either end just beats t - 2
+        let d = &t - 2u32;
+        assert_eq!(race(&t, &d), (&t - 1u32));
+
+        // with exact roots at a and b, the ways are the a + 1..b in between
+        let (a, b) = (BigUint::from(u64::MAX), BigUint::from(u64::MAX) * 3u32);
+        assert_eq!(race(&(&a + &b), &(&a * &b)), &b - &a - 1u32);
+        assert_eq!(
+            part2("Time: 18446744073709551615 000\nDistance: 0\n"),
+            Ok(t - 1u32)
+        );
+    }
+}
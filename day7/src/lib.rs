@@ -0,0 +1,102 @@
+use common::error::{parse_num, split_once};
+use common::{ParseError, ParseResult};
+use rules::Rules;
+
+pub mod rules;
+
+/// A hand and its bid. Hands are ordered by category, then card by card.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Hand<'a> {
+    /// index of the category in the rules
+    pub category: usize,
+    /// strength of every card, for breaking ties
+    strengths: Vec<usize>,
+    pub cards: &'a str,
+    pub bid: u32,
+}
+
+impl<'a> Hand<'a> {
+    /// Scores @cards by @rules, None if they don't fit any category
+    fn new(cards: &'a str, bid: u32, rules: &Rules) -> Option<Hand<'a>> {
+        Some(Hand {
+            category: rules.categorise(cards)?,
+            strengths: cards.chars().map(|c| rules.strength(c)).collect(),
+            cards,
+            bid,
+        })
+    }
+}
+
+/// Every hand in @input scored by @rules, from the lowest rank to the highest
+pub fn rank_hands<'a>(input: &'a str, rules: &Rules) -> ParseResult<Vec<Hand<'a>>> {
+    let mut hands: Vec<Hand> = Vec::new();
+
+    for (row, l) in input.lines().enumerate() {
+        if l.trim().is_empty() {
+            continue;
+        }
+
+        let (cards, bid) = split_once(row, l, " ")?;
+
+        if let Some(bad) = cards.matches(|c| !rules.is_card(c)).next() {
+            return Err(ParseError::new(row, l, bad, "a card"));
+        } else if cards.chars().count() != rules.hand_size() {
+            return Err(ParseError::new(
+                row,
+                l,
+                cards,
+                format!("a hand of {} cards", rules.hand_size()),
+            ));
+        }
+
+        let hand = Hand::new(cards, parse_num(row, l, bid.trim())?, rules)
+            .ok_or_else(|| ParseError::new(row, l, cards, "a hand in one of the categories"))?;
+        hands.push(hand);
+    }
+
+    hands.sort();
+
+    Ok(hands)
+}
+
+/// Sum of every hand's bid times its rank
+pub fn total_winnings(input: &str, rules: &Rules) -> ParseResult<u32> {
+    Ok(rank_hands(input, rules)?
+        .iter()
+        .enumerate()
+        .map(|(i, h)| (i + 1) as u32 * h.bid)
+        .sum())
+}
+
+pub fn part1(input: &str) -> ParseResult<u32> {
+    total_winnings(input, &Rules::standard())
+}
+
+pub fn part2(input: &str) -> ParseResult<u32> {
+    total_winnings(input, &Rules::jokers())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day07/example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE), Ok(6440));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), Ok(5905));
+    }
+
+    #[test]
+    fn ranks() {
+        let hands = rank_hands(EXAMPLE, &Rules::jokers()).unwrap();
+        let cards: Vec<&str> = hands.iter().map(|h| h.cards).collect();
+
+        assert_eq!(cards, ["32T3K", "KK677", "T55J5", "QQQJA", "KTJJT"]);
+    }
+}
@@ -0,0 +1,280 @@
+//! The rules hands are scored by: which cards are stronger, which are wild and what
+//! kinds of hands there are. Rules are written as text, one setting per line:
+//!
+//! ```text
+//! # weakest card first
+//! cards: 23456789TJQKA
+//! # cards that stand in for whatever card makes the best hand (optional)
+//! wild: J
+//! # weakest kind of hand first, as how many of each card it has, then its name
+//! category: 1 1 1 1 1 high card
+//! category: 2 1 1 1 one pair
+//! ```
+
+use common::error::{parse_nums, split_once};
+use common::{ParseError, ParseResult};
+
+/// Camel Cards with jacks
+pub const STANDARD: &str = "\
+cards: 23456789TJQKA
+category: 1 1 1 1 1 high card
+category: 2 1 1 1 one pair
+category: 2 2 1 two pair
+category: 3 1 1 three of a kind
+category: 3 2 full house
+category: 4 1 four of a kind
+category: 5 five of a kind
+";
+
+/// Camel Cards where J is a joker: wild, but the weakest card on its own
+pub const JOKERS: &str = "\
+cards: J23456789TQKA
+wild: J
+category: 1 1 1 1 1 high card
+category: 2 1 1 1 one pair
+category: 2 2 1 two pair
+category: 3 1 1 three of a kind
+category: 3 2 full house
+category: 4 1 four of a kind
+category: 5 five of a kind
+";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Category {
+    pub name: String,
+    /// how many of each card a hand in this category has, largest first
+    pub counts: Vec<usize>,
+}
+
+impl Category {
+    /// Whether a hand with @counts of its plain cards, largest first, and @wild wildcards
+    /// can be turned into this category. Pairing the largest counts with the largest
+    /// counts of the category is the best the wildcards can do.
+    fn fits(&self, counts: &[usize], wild: usize) -> bool {
+        counts.len() <= self.counts.len()
+            && counts.iter().zip(&self.counts).all(|(c, want)| c <= want)
+            && counts.iter().sum::<usize>() + wild == self.hand_size()
+    }
+
+    fn hand_size(&self) -> usize {
+        self.counts.iter().sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    /// cards from weakest to strongest
+    cards: Vec<char>,
+    wild: Vec<char>,
+    /// categories from weakest to strongest
+    categories: Vec<Category>,
+}
+
+impl Rules {
+    pub fn standard() -> Self {
+        Rules::parse(STANDARD).unwrap()
+    }
+
+    pub fn jokers() -> Self {
+        Rules::parse(JOKERS).unwrap()
+    }
+
+    pub fn parse(text: &str) -> ParseResult<Self> {
+        let mut cards: Option<Vec<char>> = None;
+        // the (row, line, card) of every wildcard, checked once the cards are known
+        let mut wild: Vec<(usize, &str, &str)> = Vec::new();
+        let mut categories: Vec<Category> = Vec::new();
+        let mut last_row = (0, "");
+
+        for (row, line) in text.lines().enumerate() {
+            last_row = (row, line);
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = split_once(row, line, ":")?;
+            let value = value.trim();
+
+            match key.trim() {
+                "cards" => {
+                    if let Some((i, c)) =
+                        value.char_indices().find(|(i, c)| value[..*i].contains(*c))
+                    {
+                        return Err(ParseError::new(
+                            row,
+                            line,
+                            &value[i..i + c.len_utf8()],
+                            "a card that isn't listed yet",
+                        ));
+                    }
+
+                    cards = Some(value.chars().collect());
+                }
+                "wild" => {
+                    wild = value
+                        .char_indices()
+                        .filter(|(_, c)| !c.is_whitespace())
+                        .map(|(i, c)| (row, line, &value[i..i + c.len_utf8()]))
+                        .collect()
+                }
+                "category" => {
+                    let split = value
+                        .find(|c: char| !c.is_ascii_digit() && !c.is_whitespace())
+                        .unwrap_or(value.len());
+                    let counts: Vec<usize> = parse_nums(row, line, &value[..split])?;
+                    let name = value[split..].trim();
+
+                    if counts.is_empty() || counts.contains(&0) {
+                        return Err(ParseError::new(
+                            row,
+                            line,
+                            &value[..split],
+                            "how many of each card, none 0",
+                        ));
+                    } else if name.is_empty() {
+                        return Err(ParseError::missing(row, line, "the name of the category"));
+                    } else if counts.windows(2).any(|w| w[0] < w[1]) {
+                        return Err(ParseError::new(
+                            row,
+                            line,
+                            &value[..split],
+                            "counts from largest to smallest",
+                        ));
+                    } else if categories
+                        .first()
+                        .is_some_and(|c| c.hand_size() != counts.iter().sum())
+                    {
+                        return Err(ParseError::new(
+                            row,
+                            line,
+                            &value[..split],
+                            format!(
+                                "a hand of {} cards like the first category",
+                                categories[0].hand_size()
+                            ),
+                        ));
+                    }
+
+                    categories.push(Category {
+                        name: name.to_string(),
+                        counts,
+                    });
+                }
+                _ => {
+                    return Err(ParseError::new(
+                        row,
+                        line,
+                        key,
+                        "\"cards\", \"wild\" or \"category\"",
+                    ))
+                }
+            }
+        }
+
+        let (row, line) = last_row;
+        let cards = cards.ok_or_else(|| ParseError::missing(row, line, "a \"cards:\" line"))?;
+
+        if categories.is_empty() {
+            return Err(ParseError::missing(row, line, "a \"category:\" line"));
+        }
+
+        let mut wild_cards = Vec::new();
+        for (row, line, w) in wild {
+            let c = w.chars().next().unwrap();
+
+            if !cards.contains(&c) {
+                return Err(ParseError::new(row, line, w, "one of the cards"));
+            }
+
+            wild_cards.push(c);
+        }
+
+        Ok(Rules {
+            cards,
+            wild: wild_cards,
+            categories,
+        })
+    }
+
+    /// Number of cards in a hand
+    pub fn hand_size(&self) -> usize {
+        self.categories[0].hand_size()
+    }
+
+    pub fn is_card(&self, c: char) -> bool {
+        self.cards.contains(&c)
+    }
+
+    /// Strength of @card when breaking ties, 0 for the weakest
+    pub fn strength(&self, card: char) -> usize {
+        self.cards.iter().position(|c| *c == card).unwrap()
+    }
+
+    /// Index of the strongest category @hand can be made into, None if there isn't one
+    pub fn categorise(&self, hand: &str) -> Option<usize> {
+        let mut counts: Vec<(char, usize)> = Vec::new();
+        let mut wild = 0;
+
+        for c in hand.chars() {
+            if self.wild.contains(&c) {
+                wild += 1;
+            } else if let Some(n) = counts.iter_mut().find(|(card, _)| *card == c) {
+                n.1 += 1;
+            } else {
+                counts.push((c, 1));
+            }
+        }
+
+        let mut counts: Vec<usize> = counts.into_iter().map(|(_, n)| n).collect();
+        counts.sort_by(|a, b| b.cmp(a));
+
+        self.categories
+            .iter()
+            .rposition(|cat| cat.fits(&counts, wild))
+    }
+
+    pub fn category(&self, idx: usize) -> &Category {
+        &self.categories[idx]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn categories() {
+        let standard = Rules::standard();
+        let jokers = Rules::jokers();
+        let name =
+            |rules: &Rules, hand| rules.category(rules.categorise(hand).unwrap()).name.clone();
+
+        assert_eq!(name(&standard, "KTJJT"), "two pair");
+        assert_eq!(name(&jokers, "KTJJT"), "four of a kind");
+        assert_eq!(name(&jokers, "JJJJJ"), "five of a kind");
+        assert_eq!(name(&jokers, "2345J"), "one pair");
+        assert!(jokers.strength('J') < jokers.strength('2'));
+    }
+
+    #[test]
+    fn custom_rules() {
+        // three card hands where a pair beats a run of different cards
+        let rules =
+            Rules::parse("cards: 123\ncategory: 1 1 1 mixed\ncategory: 2 1 pair\n").unwrap();
+
+        assert_eq!(rules.hand_size(), 3);
+        assert_eq!(rules.categorise("121"), Some(1));
+        assert_eq!(rules.categorise("111"), None);
+
+        let e = Rules::parse("cards: 123\nwild: 4\ncategory: 1 one\n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 7));
+
+        // only the last wild line counts
+        assert!(Rules::parse("wild: 5\ncards: 1234\nwild: 14\ncategory: 1 one\n").is_ok());
+        let e = Rules::parse("wild: 4\ncards: 123\nwild: 1 5\ncategory: 1 one\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.token.as_str()), (3, 9, "5"));
+
+        let e = Rules::parse("cards: 123\ncategory: 1 1 two\ncategory: 1 one\n").unwrap_err();
+        assert_eq!((e.line, e.column), (3, 11));
+    }
+}
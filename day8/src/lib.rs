@@ -0,0 +1,370 @@
+use common::error::split_once;
+use common::{math, ParseError, ParseResult};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone)]
+struct Node {
+    name: String,
+    left: String,
+    right: String,
+}
+
+impl Node {
+    fn new(name: String, left: String, right: String) -> Self {
+        Node { name, left, right }
+    }
+}
+
+/// Returns the instructions and the nodes of the network by name
+fn parse_network(input: &str) -> ParseResult<(&str, HashMap<String, Node>)> {
+    let lines: Vec<_> = input.lines().collect();
+    let instructions = lines.first().copied().unwrap_or("").trim();
+    let mut graph: HashMap<String, Node> = HashMap::new();
+    // every (row, line, name) a node points to, checked once all the nodes are known
+    let mut targets = Vec::new();
+
+    if let Some(bad) = instructions.matches(|c| c != 'L' && c != 'R').next() {
+        return Err(ParseError::new(0, lines[0], bad, "L or R"));
+    } else if instructions.is_empty() {
+        return Err(ParseError::missing(0, instructions, "L or R"));
+    }
+
+    for (row, line) in lines.iter().enumerate().skip(1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (name, next) = split_once(row, line, " = ")?;
+        let (left, right) = next
+            .strip_prefix('(')
+            .and_then(|n| n.strip_suffix(')'))
+            .and_then(|n| n.split_once(", "))
+            .ok_or_else(|| ParseError::new(row, line, next, "\"(<left>, <right>)\""))?;
+
+        targets.push((row, *line, left));
+        targets.push((row, *line, right));
+        graph.insert(
+            name.to_string(),
+            Node::new(name.to_string(), left.to_string(), right.to_string()),
+        );
+    }
+
+    for (row, line, name) in targets {
+        if !graph.contains_key(name) {
+            return Err(ParseError::new(row, line, name, "the name of a node"));
+        }
+    }
+
+    Ok((instructions, graph))
+}
+
+/// Number of steps it takes to walk from @start to the first node that satisfies
+/// @is_end, None if a (node, instruction index) repeats first as the walk then goes
+/// round forever
+fn count_steps(
+    start: &Node,
+    instructions: &str,
+    graph: &HashMap<String, Node>,
+    is_end: impl Fn(&str) -> bool,
+) -> Option<u64> {
+    let instructions: Vec<char> = instructions.chars().collect();
+    let mut seen: HashSet<(&str, usize)> = HashSet::new();
+    let mut s = start;
+    let mut steps = 0;
+
+    // there are only so many (node, index) pairs, so this ends
+    loop {
+        let idx = (steps % instructions.len() as u64) as usize;
+
+        if !seen.insert((&s.name, idx)) {
+            return None;
+        }
+
+        let next = if instructions[idx] == 'L' {
+            &s.left
+        } else {
+            &s.right
+        };
+        s = &graph[next];
+        steps += 1;
+
+        if is_end(&s.name) {
+            return Some(steps);
+        }
+    }
+}
+
+/// The steps at which a ghost is on a node ending in Z. Its walk is in a cycle of
+/// `period` steps from step `offset` on.
+#[derive(Debug, PartialEq, Eq)]
+struct Ghost {
+    offset: u64,
+    period: u64,
+    /// steps before the cycle starts
+    hits: Vec<u64>,
+    /// steps in the first time around the cycle, which repeat every `period` steps
+    cycle_hits: Vec<u64>,
+}
+
+impl Ghost {
+    /// Walks from @start until a (node, instruction index) repeats, after which the walk
+    /// is the same forever
+    fn walk(start: &Node, instructions: &str, graph: &HashMap<String, Node>) -> Self {
+        let instructions: Vec<char> = instructions.chars().collect();
+        let mut seen: HashMap<(&str, usize), u64> = HashMap::new();
+        let mut z_steps = Vec::new();
+        let mut node = start;
+
+        for step in 0.. {
+            let idx = (step % instructions.len() as u64) as usize;
+
+            if let Some(&first) = seen.get(&(node.name.as_str(), idx)) {
+                let (hits, cycle_hits) = z_steps.into_iter().partition(|s| *s < first);
+
+                return Ghost {
+                    offset: first,
+                    period: step - first,
+                    hits,
+                    cycle_hits,
+                };
+            }
+
+            seen.insert((&node.name, idx), step);
+            if node.name.ends_with('Z') {
+                z_steps.push(step);
+            }
+
+            let next = if instructions[idx] == 'L' {
+                &node.left
+            } else {
+                &node.right
+            };
+            node = &graph[next];
+        }
+
+        unreachable!()
+    }
+
+    fn is_on_z(&self, step: u64) -> bool {
+        if step < self.offset {
+            self.hits.contains(&step)
+        } else {
+            let in_cycle = self.offset + (step - self.offset) % self.period;
+
+            self.cycle_hits.contains(&in_cycle)
+        }
+    }
+}
+
+/// First step, after the start, at which every one of @ghosts is on a Z node
+fn all_on_z(ghosts: &[Ghost]) -> Option<u64> {
+    // some ghost hasn't reached its cycle yet, so it is one of that ghost's hits
+    let early = ghosts
+        .iter()
+        .flat_map(|g| g.hits.iter().copied())
+        .filter(|s| *s > 0 && ghosts.iter().all(|g| g.is_on_z(*s)))
+        .min();
+
+    // every ghost is in its cycle, so line up a hit in each cycle. Going one ghost at a
+    // time, keep the steps (mod the lcm of the periods so far) at which all the ghosts
+    // so far are on a Z node, dropping those whose lcm doesn't fit in a u64.
+    let start = ghosts.iter().map(|g| g.offset).max().unwrap_or(0).max(1) as u128;
+    let mut residues: Vec<(u64, u64)> = vec![(0, 1)];
+
+    for g in ghosts {
+        residues = residues
+            .iter()
+            .flat_map(|&r| g.cycle_hits.iter().map(move |&h| (r, (h, g.period))))
+            .filter_map(|(r, h)| math::crt(&[r, h]))
+            .filter_map(|(x, m)| Some((u64::try_from(x).ok()?, u64::try_from(m).ok()?)))
+            .collect();
+        residues.sort_unstable();
+        residues.dedup();
+    }
+
+    let cyclic = residues
+        .into_iter()
+        .map(|(x, m)| (x as u128, m as u128))
+        .map(|(x, m)| {
+            if x < start {
+                x + (start - x).div_ceil(m) * m
+            } else {
+                x
+            }
+        })
+        .filter_map(|s| u64::try_from(s).ok())
+        .min();
+
+    early.into_iter().chain(cyclic).min()
+}
+
+pub fn part1(input: &str) -> ParseResult<u64> {
+    let (instructions, graph) = parse_network(input)?;
+
+    for name in ["AAA", "ZZZ"] {
+        if !graph.contains_key(name) {
+            let (row, last) = input.lines().enumerate().last().unwrap();
+
+            return Err(ParseError::missing(
+                row,
+                last,
+                format!("a node named {}", name),
+            ));
+        }
+    }
+
+    count_steps(&graph["AAA"], instructions, &graph, |n| n == "ZZZ").ok_or_else(|| {
+        let (row, last) = input.lines().enumerate().last().unwrap();
+
+        ParseError::missing(row, last, "a network where AAA leads to ZZZ")
+    })
+}
+
+pub fn part2(input: &str) -> ParseResult<u64> {
+    let (instructions, graph) = parse_network(input)?;
+    let ghosts: Vec<Ghost> = graph
+        .values()
+        .filter(|n| n.name.ends_with('A'))
+        .map(|s| Ghost::walk(s, instructions, &graph))
+        .collect();
+
+    if ghosts.is_empty() {
+        let (row, last) = input.lines().enumerate().last().unwrap();
+
+        return Err(ParseError::missing(row, last, "a node ending in A"));
+    }
+
+    all_on_z(&ghosts).ok_or_else(|| {
+        let (row, last) = input.lines().enumerate().last().unwrap();
+
+        ParseError::missing(row, last, "ghosts that are all on a Z node at some step")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE1: &str = include_str!("../../inputs/day08/example1.txt");
+    const EXAMPLE2: &str = include_str!("../../inputs/day08/example2.txt");
+    const EXAMPLE3: &str = include_str!("../../inputs/day08/example3.txt");
+
+    #[test]
+    fn part1_example1() {
+        assert_eq!(part1(EXAMPLE1), Ok(2));
+    }
+
+    #[test]
+    fn part1_example2() {
+        assert_eq!(part1(EXAMPLE2), Ok(6));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE3), Ok(6));
+    }
+
+    #[test]
+    fn ghost_cycles() {
+        let (instructions, graph) = parse_network(EXAMPLE3).unwrap();
+        let ghost = Ghost::walk(&graph["22A"], instructions, &graph);
+
+        assert_eq!(
+            ghost,
+            Ghost {
+                offset: 1,
+                period: 6,
+                hits: vec![],
+                cycle_hits: vec![3, 6],
+            }
+        );
+        assert!(ghost.is_on_z(9) && !ghost.is_on_z(10));
+    }
+
+    /// 11A is on 11Z every other step from step 1, 22A every third step from step 2,
+    /// so they first line up at step 5 rather than at the lcm of their first steps on Z
+    const OFFSET_CYCLES: &str = "L
+
+11A = (11Z, XXX)
+11Z = (11B, XXX)
+11B = (11Z, XXX)
+22A = (22B, XXX)
+22B = (22Z, XXX)
+22Z = (22C, XXX)
+22C = (22D, XXX)
+22D = (22Z, XXX)
+XXX = (XXX, XXX)
+";
+
+    /// 33A is only ever on 33Z at step 1, before it gets stuck on 33B
+    const BEFORE_CYCLES: &str = "L
+
+11A = (11Z, XXX)
+11Z = (11B, XXX)
+11B = (11Z, XXX)
+33A = (33Z, XXX)
+33Z = (33B, XXX)
+33B = (33B, XXX)
+XXX = (XXX, XXX)
+";
+
+    /// 11A is on 11Z at odd steps and 22A on 22Z at even ones
+    const NEVER_LINED_UP: &str = "L
+
+11A = (11Z, XXX)
+11Z = (11A, XXX)
+22A = (22B, XXX)
+22B = (22Z, XXX)
+22Z = (22B, XXX)
+XXX = (XXX, XXX)
+";
+
+    #[test]
+    fn zzz_unreachable() {
+        let e = part1("LR\n\nAAA = (BBB, AAA)\nBBB = (AAA, BBB)\nZZZ = (ZZZ, ZZZ)\n").unwrap_err();
+
+        assert_eq!((e.line, e.column), (5, 17));
+    }
+
+    #[test]
+    fn never_lined_up() {
+        let e = part2(NEVER_LINED_UP).unwrap_err();
+
+        assert_eq!((e.line, e.column), (8, 17));
+    }
+
+    #[test]
+    fn many_ghosts() {
+        // 2^30 ways to pick a hit from every cycle, but only 2 steps mod 6 they share
+        let ghosts: Vec<Ghost> = (0..30)
+            .map(|_| Ghost {
+                offset: 1,
+                period: 6,
+                hits: vec![],
+                cycle_hits: vec![2, 5],
+            })
+            .collect();
+
+        assert_eq!(all_on_z(&ghosts), Some(2));
+    }
+
+    #[test]
+    fn offset_cycles() {
+        let (instructions, graph) = parse_network(OFFSET_CYCLES).unwrap();
+        let ghost = Ghost::walk(&graph["22A"], instructions, &graph);
+
+        assert_eq!((ghost.offset, ghost.period), (2, 3));
+        assert_eq!(ghost.cycle_hits, [2]);
+        assert_eq!(part2(OFFSET_CYCLES), Ok(5));
+    }
+
+    #[test]
+    fn before_cycles() {
+        let (instructions, graph) = parse_network(BEFORE_CYCLES).unwrap();
+        let ghost = Ghost::walk(&graph["33A"], instructions, &graph);
+
+        assert_eq!((ghost.offset, ghost.period, ghost.hits), (2, 1, vec![1]));
+        assert!(ghost.cycle_hits.is_empty());
+        assert_eq!(part2(BEFORE_CYCLES), Ok(1));
+    }
+}
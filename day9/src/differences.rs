@@ -0,0 +1,135 @@
+//! Finite differences of a sequence sampled at x = 0, 1, 2, ... Every sequence of n
+//! values is fit exactly by a polynomial of degree at most n - 1, which can be
+//! evaluated anywhere to extrapolate it in either direction.
+
+use std::error::Error;
+use std::fmt::Display;
+
+/// An i64 overflowed while computing differences or extrapolating
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "overflowed an i64")
+    }
+}
+
+impl Error for Overflow {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sequence {
+    values: Vec<i64>,
+    /// first value of every row of the difference table, up to the first row that is
+    /// all zeros. These are the coefficients of the Newton polynomial.
+    coefficients: Vec<i64>,
+}
+
+impl Sequence {
+    pub fn new(values: Vec<i64>) -> Result<Self, Overflow> {
+        let mut coefficients = Vec::new();
+        let mut row = values.clone();
+
+        while row.iter().any(|n| *n != 0) {
+            coefficients.push(row[0]);
+
+            row = row
+                .windows(2)
+                .map(|w| w[1].checked_sub(w[0]).ok_or(Overflow))
+                .collect::<Result<_, _>>()?;
+        }
+
+        Ok(Sequence {
+            values,
+            coefficients,
+        })
+    }
+
+    pub fn values(&self) -> &[i64] {
+        &self.values
+    }
+
+    /// Degree of the lowest degree polynomial through the values. Constants, including
+    /// the all zero sequence, are degree 0.
+    pub fn degree(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    /// Coefficients a_k of the Newton polynomial through the values, so the value at x
+    /// is the sum of a_k * (x choose k)
+    pub fn newton_coefficients(&self) -> &[i64] {
+        &self.coefficients
+    }
+
+    /// Value of the polynomial at @x, which may be before the first value (negative)
+    /// or after the last
+    pub fn value_at(&self, x: i64) -> Result<i64, Overflow> {
+        // terms can cancel out, so only the sum has to fit in an i64
+        let mut sum: i128 = 0;
+        // x choose k, worked out as x (x - 1) ... (x - k + 1) / k! one factor at a time
+        let mut choose: i128 = 1;
+
+        for (k, a) in self.coefficients.iter().enumerate() {
+            if k > 0 {
+                // divides exactly since each step is itself a binomial coefficient
+                choose = choose
+                    .checked_mul(x as i128 - k as i128 + 1)
+                    .ok_or(Overflow)?
+                    / k as i128;
+            }
+
+            let term = choose.checked_mul(*a as i128).ok_or(Overflow)?;
+            sum = sum.checked_add(term).ok_or(Overflow)?;
+        }
+
+        i64::try_from(sum).map_err(|_| Overflow)
+    }
+
+    /// The @n values after the last one
+    pub fn forwards(&self, n: usize) -> Result<Vec<i64>, Overflow> {
+        let len = self.values.len() as i64;
+
+        (0..n as i64).map(|i| self.value_at(len + i)).collect()
+    }
+
+    /// The @n values before the first one, nearest first
+    pub fn backwards(&self, n: usize) -> Result<Vec<i64>, Overflow> {
+        (1..=n as i64).map(|i| self.value_at(-i)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polynomial() {
+        // x^2 + 1
+        let s = Sequence::new(vec![1, 2, 5, 10, 17]).unwrap();
+
+        assert_eq!(s.degree(), 2);
+        assert_eq!(s.newton_coefficients(), [1, 1, 2]);
+        assert_eq!(s.forwards(3), Ok(vec![26, 37, 50]));
+        assert_eq!(s.backwards(3), Ok(vec![2, 5, 10]));
+        assert_eq!(s.value_at(2), Ok(5));
+    }
+
+    #[test]
+    fn constants() {
+        assert_eq!(Sequence::new(vec![0, 0]).unwrap().degree(), 0);
+        assert_eq!(
+            Sequence::new(vec![4, 4]).unwrap().forwards(2),
+            Ok(vec![4, 4])
+        );
+        assert_eq!(Sequence::new(vec![7]).unwrap().backwards(1), Ok(vec![7]));
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!(Sequence::new(vec![i64::MIN, i64::MAX]), Err(Overflow));
+
+        let s = Sequence::new(vec![0, i64::MAX / 2]).unwrap();
+        assert_eq!(s.forwards(1), Ok(vec![i64::MAX - 1]));
+        assert_eq!(s.forwards(2), Err(Overflow));
+    }
+}
@@ -0,0 +1,81 @@
+use common::error::parse_nums;
+use common::{ParseError, ParseResult};
+use differences::Sequence;
+
+pub mod differences;
+
+fn parse_line(row: usize, line: &str) -> ParseResult<Sequence> {
+    let values = parse_nums(row, line, line)?;
+
+    if values.is_empty() {
+        return Err(ParseError::missing(row, line, "a number"));
+    }
+
+    Sequence::new(values).map_err(|_| overflow(row, line))
+}
+
+fn overflow(row: usize, line: &str) -> ParseError {
+    ParseError::new(
+        row,
+        line,
+        line.trim(),
+        "a sequence that extrapolates without overflowing an i64",
+    )
+}
+
+/// Sum of the values @steps after the end of every sequence, or before the start if
+/// @steps is negative
+pub fn sum_extrapolated(input: &str, steps: i64) -> ParseResult<i64> {
+    let mut sum: i64 = 0;
+
+    for (row, l) in input.lines().enumerate() {
+        let seq = parse_line(row, l)?;
+        let x = if steps < 0 {
+            steps
+        } else {
+            seq.values().len() as i64 - 1 + steps
+        };
+        let value = seq.value_at(x).map_err(|_| overflow(row, l))?;
+
+        sum = sum.checked_add(value).ok_or_else(|| overflow(row, l))?;
+    }
+
+    Ok(sum)
+}
+
+pub fn part1(input: &str) -> ParseResult<i64> {
+    sum_extrapolated(input, 1)
+}
+
+pub fn part2(input: &str) -> ParseResult<i64> {
+    sum_extrapolated(input, -1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../inputs/day09/example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE), Ok(114));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE), Ok(2));
+    }
+
+    #[test]
+    fn further_steps() {
+        // the second values after the ends are 21, 36 and 101
+        assert_eq!(sum_extrapolated(EXAMPLE, 2), Ok(21 + 36 + 101));
+        assert_eq!(
+            sum_extrapolated("9223372036854775800 9223372036854775807\n", 1)
+                .unwrap_err()
+                .column,
+            1
+        );
+    }
+}
@@ -0,0 +1,279 @@
+//! Directions, points and vectors on the integer plane. Like the grid, y grows
+//! downwards, so `Direction::Up` is a step to the row above.
+
+use common::{ParseError, ParseResult};
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// Every direction, in the same order as `Grid::neighbours4`
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    /// The direction after a quarter turn counter clockwise
+    pub fn turn_left(self) -> Self {
+        use Direction::*;
+
+        match self {
+            Up => Left,
+            Left => Down,
+            Down => Right,
+            Right => Up,
+        }
+    }
+
+    /// The direction after a quarter turn clockwise
+    pub fn turn_right(self) -> Self {
+        self.turn_left().reverse()
+    }
+
+    pub fn reverse(self) -> Self {
+        use Direction::*;
+
+        match self {
+            Up => Down,
+            Down => Up,
+            Left => Right,
+            Right => Left,
+        }
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Direction::Left | Direction::Right)
+    }
+
+    /// A step of length 1 in this direction
+    pub fn delta(self) -> Vector {
+        match self {
+            Direction::Up => Vector::new(0, -1),
+            Direction::Down => Vector::new(0, 1),
+            Direction::Left => Vector::new(-1, 0),
+            Direction::Right => Vector::new(1, 0),
+        }
+    }
+
+    /// The direction of the single step from @from to @to, None if they aren't
+    /// orthogonal neighbours
+    pub fn between(from: impl Into<Point>, to: impl Into<Point>) -> Option<Self> {
+        let delta = to.into() - from.into();
+
+        Direction::ALL.into_iter().find(|d| d.delta() == delta)
+    }
+
+    /// Parses @c as one of U/D/L/R, N/S/E/W or the arrows ^/v/</>
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'U' | 'N' | '^' => Some(Direction::Up),
+            'D' | 'S' | 'v' => Some(Direction::Down),
+            'L' | 'W' | '<' => Some(Direction::Left),
+            'R' | 'E' | '>' => Some(Direction::Right),
+            _ => None,
+        }
+    }
+
+    /// Parses @token, a slice of the @row'th @line, with `from_char`
+    pub fn parse(row: usize, line: &str, token: &str) -> ParseResult<Self> {
+        let mut chars = token.chars();
+
+        match (chars.next().and_then(Direction::from_char), chars.next()) {
+            (Some(d), None) => Ok(d),
+            _ => Err(ParseError::new(
+                row,
+                line,
+                token,
+                "a direction (U/D/L/R, N/S/E/W or ^/v/</>)",
+            )),
+        }
+    }
+
+    /// The arrow pointing this way
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Right => '>',
+        }
+    }
+}
+
+/// Prints the arrow pointing in the direction
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.arrow())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+
+    pub fn manhattan(self, other: Point) -> u64 {
+        (other - self).manhattan()
+    }
+
+    /// The point one step away in @dir
+    pub fn step(self, dir: Direction) -> Self {
+        self + dir.delta()
+    }
+}
+
+/// A grid (row, column) position, so x is the column and y the row
+impl From<(usize, usize)> for Point {
+    fn from(pos: (usize, usize)) -> Self {
+        Point::new(pos.1 as i64, pos.0 as i64)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// The difference between two `Point`s
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vector {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Vector {
+    pub fn new(x: i64, y: i64) -> Self {
+        Vector { x, y }
+    }
+
+    /// Length of the vector moving only along the axes
+    pub fn manhattan(self) -> u64 {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, v: Vector) -> Point {
+        Point::new(self.x + v.x, self.y + v.y)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, v: Vector) {
+        *self = *self + v;
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, v: Vector) -> Point {
+        Point::new(self.x - v.x, self.y - v.y)
+    }
+}
+
+impl SubAssign<Vector> for Point {
+    fn sub_assign(&mut self, v: Vector) {
+        *self = *self - v;
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, other: Point) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        Vector::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, other: Vector) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, n: i64) -> Vector {
+        Vector::new(self.x * n, self.y * n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        for d in Direction::ALL {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.turn_right().turn_right(), d.reverse());
+            assert_eq!(d.delta(), -d.reverse().delta());
+        }
+
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::between((1, 1), (0, 1)), Some(Direction::Up));
+        assert_eq!(Direction::between((1, 1), (0, 0)), None);
+    }
+
+    #[test]
+    fn parse() {
+        for (s, d) in [
+            ("U", Direction::Up),
+            ("S", Direction::Down),
+            ("<", Direction::Left),
+        ] {
+            assert_eq!(Direction::parse(0, s, s), Ok(d));
+        }
+
+        assert!(Direction::parse(0, "X", "X").is_err());
+        assert!(Direction::parse(0, "UU", "UU").is_err());
+    }
+
+    #[test]
+    fn arithmetic() {
+        let p = Point::new(1, 2) + Direction::Left.delta() * 3;
+
+        assert_eq!(p, Point::new(-2, 2));
+        assert_eq!(p - Point::new(0, 0), Vector::new(-2, 2));
+        assert_eq!(p.manhattan(Point::new(1, -1)), 6);
+        assert_eq!(Point::from((2, 5)).step(Direction::Down), Point::new(5, 3));
+    }
+}
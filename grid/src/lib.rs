@@ -0,0 +1,377 @@
+//! A 2D grid of cells, for the days whose input is a character map.
+
+pub mod geometry;
+pub mod polygon;
+
+pub use geometry::{Direction, Point, Vector};
+pub use polygon::Polygon;
+
+use common::{ParseError, ParseResult};
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+/// A (row, column) position in a grid
+pub type Pos = (usize, usize);
+
+/// Offsets of all 8 neighbours as (row, column), row by row
+const ALL: [(i64, i64); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A rectangular grid stored row by row in a single `Vec`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a @width by @height grid where every cell is @fill
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Creates a grid from @cells stored row by row, @width cells to a row. Panics if
+    /// @cells can't be split into rows of @width.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        assert_eq!(width * height, cells.len(), "cells don't fill the last row");
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses the lines of @input into a grid, converting every character with @cell.
+    /// When @cell returns None the character is reported as not being @expected, as
+    /// is any line that is longer or shorter than the first.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> ParseResult<Self> {
+        let lines = common::error::grid_lines(input)?;
+        let width = lines.first().map_or(0, |l| l.chars().count());
+        let mut cells = Vec::with_capacity(width * lines.len());
+
+        for (row, line) in lines.iter().enumerate() {
+            for (i, c) in line.char_indices() {
+                let token = &line[i..i + c.len_utf8()];
+
+                cells.push(cell(c).ok_or_else(|| ParseError::new(row, line, token, expected))?);
+            }
+        }
+
+        Ok(Grid {
+            width,
+            height: lines.len(),
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Whether @pos is inside the grid
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.0 < self.height && pos.1 < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.0 * self.width + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.0 * self.width + pos.1])
+        } else {
+            None
+        }
+    }
+
+    /// Converts the signed (row, column) @pos to a position, if it is inside the grid
+    pub fn to_pos(&self, pos: (i64, i64)) -> Option<Pos> {
+        let pos = (usize::try_from(pos.0).ok()?, usize::try_from(pos.1).ok()?);
+
+        self.contains(pos).then_some(pos)
+    }
+
+    /// Like `get`, but with a signed (row, column) that may be off the grid
+    pub fn get_signed(&self, pos: (i64, i64)) -> Option<&T> {
+        self.to_pos(pos).and_then(|p| self.get(p))
+    }
+
+    /// Position @offset away from @pos, if it is inside the grid
+    pub fn offset(&self, pos: Pos, offset: (i64, i64)) -> Option<Pos> {
+        self.to_pos((pos.0 as i64 + offset.0, pos.1 as i64 + offset.1))
+    }
+
+    /// Position one step from @pos in @dir, if it is inside the grid
+    pub fn step(&self, pos: Pos, dir: Direction) -> Option<Pos> {
+        let delta = dir.delta();
+
+        self.offset(pos, (delta.y, delta.x))
+    }
+
+    /// Converts @point to a position, if it is inside the grid
+    pub fn point_pos(&self, point: Point) -> Option<Pos> {
+        self.to_pos((point.y, point.x))
+    }
+
+    /// The up to 4 orthogonal neighbours of @pos, in the order up, down, left, right
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(pos, d))
+    }
+
+    /// The up to 8 neighbours of @pos, including the diagonal ones, row by row
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ALL.iter().filter_map(move |o| self.offset(pos, *o))
+    }
+
+    /// Every position in the grid, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+
+        (0..self.height).flat_map(move |r| (0..width).map(move |c| (r, c)))
+    }
+
+    /// Every cell and its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Every cell, row by row
+    pub fn cells(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// Position of the first cell, row by row, that satisfies @pred
+    pub fn position(&self, pred: impl Fn(&T) -> bool) -> Option<Pos> {
+        self.iter().find(|(_, t)| pred(t)).map(|(p, _)| p)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on 0, but there are no rows to return then anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(col).step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|c| self.column(c))
+    }
+
+    /// Applies @f to every cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// The grid repeated @across times horizontally and @down times vertically
+    pub fn tiled(&self, across: usize, down: usize) -> Self
+    where
+        T: Clone,
+    {
+        let mut cells = Vec::with_capacity(self.cells.len() * across * down);
+
+        for _ in 0..down {
+            for row in self.rows() {
+                for _ in 0..across {
+                    cells.extend_from_slice(row);
+                }
+            }
+        }
+
+        Grid {
+            width: self.width * across,
+            height: self.height * down,
+            cells,
+        }
+    }
+
+    /// The grid flipped over its main diagonal, so rows become columns
+    pub fn transposed(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+
+    /// The grid rotated a quarter turn clockwise
+    pub fn rotated_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let mut cells = Vec::with_capacity(self.cells.len());
+
+        for c in 0..self.width {
+            for r in (0..self.height).rev() {
+                cells.push(self[(r, c)].clone());
+            }
+        }
+
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    /// The grid rotated a quarter turn counter clockwise
+    pub fn rotated_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let mut cells = Vec::with_capacity(self.cells.len());
+
+        for c in (0..self.width).rev() {
+            for r in 0..self.height {
+                cells.push(self[(r, c)].clone());
+            }
+        }
+
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        assert!(self.contains(pos), "{:?} is outside the grid", pos);
+
+        &self.cells[pos.0 * self.width + pos.1]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        assert!(self.contains(pos), "{:?} is outside the grid", pos);
+
+        &mut self.cells[pos.0 * self.width + pos.1]
+    }
+}
+
+/// Prints every cell with no separator, one row per line
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef\n", "a letter", Some).unwrap()
+    }
+
+    #[test]
+    fn parse_and_index() {
+        let g = grid();
+
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g[(1, 2)], 'f');
+        assert_eq!(g.get((2, 0)), None);
+        assert_eq!(g.get_signed((-1, 0)), None);
+        assert_eq!(g.get_signed((0, 1)), Some(&'b'));
+        assert_eq!(g.to_string(), "abc\ndef\n");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let e = Grid::parse("..\n.x\n", "\".\"", |c| (c == '.').then_some(())).unwrap_err();
+        assert_eq!((e.line, e.column, e.token.as_str()), (2, 2, "x"));
+
+        let e = Grid::parse("..\n.\n", "\".\"", |c| (c == '.').then_some(())).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 2));
+    }
+
+    #[test]
+    fn neighbours() {
+        let g = grid();
+
+        assert_eq!(g.neighbours4((0, 0)).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+        assert_eq!(g.neighbours4((1, 1)).count(), 3);
+        assert_eq!(g.neighbours8((0, 1)).count(), 5);
+    }
+
+    #[test]
+    fn views() {
+        let g = grid();
+
+        assert_eq!(g.row(1), ['d', 'e', 'f']);
+        assert_eq!(g.column(2).collect::<String>(), "cf");
+        assert_eq!(g.transposed().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(g.rotated_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(g.rotated_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(g.rotated_cw().rotated_ccw(), g);
+        assert_eq!(
+            g.tiled(2, 2).to_string(),
+            "abcabc\ndefdef\nabcabc\ndefdef\n"
+        );
+    }
+}
@@ -0,0 +1,316 @@
+//! Polygons with their corners on whole numbered points.
+
+use crate::{Point, Vector};
+use common::math::gcd;
+
+/// Where a point is compared to a polygon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Containment {
+    Inside,
+    /// on one of the edges
+    Boundary,
+    Outside,
+}
+
+/// A simple polygon through a list of corners, closed from the last corner back to
+/// the first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    corners: Vec<Point>,
+}
+
+impl Polygon {
+    pub fn new(corners: Vec<Point>) -> Self {
+        Polygon { corners }
+    }
+
+    pub fn corners(&self) -> &[Point] {
+        &self.corners
+    }
+
+    /// Every edge as (from, to), including the one that closes the polygon
+    pub fn edges(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        self.corners
+            .iter()
+            .copied()
+            .zip(self.corners.iter().copied().cycle().skip(1))
+    }
+
+    /// Twice the area by the shoelace formula, which is always a whole number
+    pub fn twice_area(&self) -> u64 {
+        self.edges()
+            .map(|(a, b)| a.x as i128 * b.y as i128 - b.x as i128 * a.y as i128)
+            .sum::<i128>()
+            .unsigned_abs() as u64
+    }
+
+    /// Length of the outline, the same as `boundary_points` when every edge is
+    /// horizontal or vertical and the outline doesn't go back over itself
+    pub fn perimeter(&self) -> f64 {
+        self.edges()
+            .map(|(a, b)| {
+                let d = b - a;
+
+                ((d.x * d.x + d.y * d.y) as f64).sqrt()
+            })
+            .sum()
+    }
+
+    /// For every edge, the number of whole numbered points on it that no earlier edge
+    /// went through. Compares every pair of edges, O(n^2).
+    fn new_points(&self) -> Vec<u64> {
+        let edges: Vec<(Point, Point)> = self.edges().collect();
+
+        edges
+            .iter()
+            .enumerate()
+            .map(|(i, &(a, b))| {
+                let g = gcd(a.x.abs_diff(b.x), a.y.abs_diff(b.y));
+                // the points on this edge are a + k * step for k in 0..=g
+                let step = if g == 0 {
+                    b - a
+                } else {
+                    Vector::new((b.x - a.x) / g as i64, (b.y - a.y) / g as i64)
+                };
+
+                let mut covered: Vec<(u64, u64)> = edges[..i]
+                    .iter()
+                    .filter_map(|&(c, d)| shared_points(a, step, g, c, d))
+                    .collect();
+                covered.sort_unstable();
+
+                // merge the ranges of points earlier edges already went through
+                let mut seen = 0;
+                let mut next = 0;
+                for (lo, hi) in covered {
+                    let lo = lo.max(next);
+
+                    if lo <= hi {
+                        seen += hi - lo + 1;
+                        next = hi + 1;
+                    }
+                }
+
+                g + 1 - seen
+            })
+            .collect()
+    }
+
+    /// Whole numbered points on the outline, each counted once even where the outline
+    /// goes back over itself
+    pub fn boundary_points(&self) -> u64 {
+        self.new_points().iter().sum()
+    }
+
+    /// Index of the first edge, the one from `corners()[i]`, that goes through a whole
+    /// numbered point an earlier edge did, other than the corner it starts from (and
+    /// for the last edge the first corner). None if the outline never goes back over
+    /// itself that way, which Pick's theorem needs.
+    pub fn overlapping_edge(&self) -> Option<usize> {
+        let n = self.corners.len();
+
+        self.edges()
+            .zip(self.new_points())
+            .enumerate()
+            .position(|(i, ((a, b), new))| {
+                let shared = u64::from(i > 0) + u64::from(i > 0 && i == n - 1);
+
+                new + shared < gcd(a.x.abs_diff(b.x), a.y.abs_diff(b.y)) + 1
+            })
+    }
+
+    /// Whole numbered points strictly inside, by Pick's theorem:
+    /// area = inside + boundary / 2 - 1. Only holds when `overlapping_edge` is None;
+    /// otherwise this is a guess that is never below 0.
+    pub fn interior_points(&self) -> u64 {
+        (self.twice_area() + 2).saturating_sub(self.boundary_points()) / 2
+    }
+
+    /// Whole numbered points inside or on the outline
+    pub fn lattice_points(&self) -> u64 {
+        if self.corners.is_empty() {
+            return 0;
+        }
+
+        self.interior_points() + self.boundary_points()
+    }
+
+    /// Whether @p is inside, on or outside the polygon, by counting the edges a ray
+    /// from @p to the right crosses
+    pub fn contains(&self, p: Point) -> Containment {
+        let mut inside = false;
+
+        for (a, b) in self.edges() {
+            let (d, e) = (b - a, p - a);
+            let cross = d.x as i128 * e.y as i128 - d.y as i128 * e.x as i128;
+            let between = p.x >= a.x.min(b.x)
+                && p.x <= a.x.max(b.x)
+                && p.y >= a.y.min(b.y)
+                && p.y <= a.y.max(b.y);
+
+            if cross == 0 && between {
+                return Containment::Boundary;
+            }
+
+            // edges count when they go from one side of the ray to the other, with
+            // their lower end counted as above the ray so corners count once
+            if (a.y > p.y) != (b.y > p.y) {
+                // the crossing is to the right when @p is on the left of the edge
+                // going upwards, or the right going downwards
+                let right = if b.y > a.y { cross > 0 } else { cross < 0 };
+
+                inside ^= right;
+            }
+        }
+
+        if inside {
+            Containment::Inside
+        } else {
+            Containment::Outside
+        }
+    }
+}
+
+fn cross(u: Vector, v: Vector) -> i128 {
+    u.x as i128 * v.y as i128 - u.y as i128 * v.x as i128
+}
+
+/// The range of k in 0..=@g for which a + k * @step, a point on an edge from @a, is
+/// also on the edge from @c to @d. @step is either 0 (@g is 0 as well) or has no common
+/// factor, so every whole numbered point on the edge's line is one of them.
+fn shared_points(a: Point, step: Vector, g: u64, c: Point, d: Point) -> Option<(u64, u64)> {
+    let on_cd = |p: Point| {
+        cross(d - c, p - c) == 0
+            && p.x >= c.x.min(d.x)
+            && p.x <= c.x.max(d.x)
+            && p.y >= c.y.min(d.y)
+            && p.y <= c.y.max(d.y)
+    };
+
+    if g == 0 {
+        return on_cd(a).then_some((0, 0));
+    }
+
+    let along = |p: Point| {
+        let v = p - a;
+
+        (v.x as i128 * step.x as i128 + v.y as i128 * step.y as i128)
+            / (step.x as i128 * step.x as i128 + step.y as i128 * step.y as i128)
+    };
+    let denom = cross(step, d - c);
+
+    let (lo, hi) = if denom == 0 {
+        // parallel, so they only share points if they are on the same line
+        if cross(step, c - a) != 0 {
+            return None;
+        }
+
+        let (kc, kd) = (along(c), along(d));
+
+        (kc.min(kd), kc.max(kd))
+    } else {
+        // crossing lines, which share a point if it is a whole numbered one
+        let num = cross(c - a, d - c);
+
+        if num % denom != 0 {
+            return None;
+        }
+
+        let k = num / denom;
+        if k < 0 || k > g as i128 || !on_cd(a + step * k as i64) {
+            return None;
+        }
+
+        (k, k)
+    };
+
+    let (lo, hi) = (lo.max(0), hi.min(g as i128));
+
+    (lo <= hi).then_some((lo as u64, hi as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn square() {
+        // 4x4 with a notch cut out of the top edge, y downwards
+        let p = Polygon::new(
+            [
+                (0, 0),
+                (1, 0),
+                (1, 1),
+                (2, 1),
+                (2, 0),
+                (4, 0),
+                (4, 4),
+                (0, 4),
+            ]
+            .iter()
+            .map(|(x, y)| Point::new(*x, *y))
+            .collect(),
+        );
+
+        assert_eq!(p.twice_area(), 30);
+        assert_eq!(p.boundary_points(), 18);
+        assert_eq!(p.perimeter(), 18.0);
+        assert_eq!(p.interior_points(), 7);
+        assert_eq!(p.lattice_points(), 25);
+        assert_eq!(p.overlapping_edge(), None);
+
+        assert_eq!(p.contains(Point::new(3, 3)), Containment::Inside);
+        assert_eq!(p.contains(Point::new(1, 1)), Containment::Boundary);
+        assert_eq!(p.contains(Point::new(4, 2)), Containment::Boundary);
+        assert_eq!(p.contains(Point::new(5, 2)), Containment::Outside);
+        // level with the notch's corners
+        assert_eq!(p.contains(Point::new(-1, 1)), Containment::Outside);
+        assert_eq!(p.contains(Point::new(3, 1)), Containment::Inside);
+    }
+
+    #[test]
+    fn slanted() {
+        // right triangle with legs of 4, by Pick 2*8 = 2*I + 12 - 2
+        let p = Polygon::new(vec![Point::new(0, 0), Point::new(4, 0), Point::new(0, 4)]);
+
+        assert_eq!(p.boundary_points(), 12);
+        assert_eq!(p.interior_points(), 3);
+        assert_eq!(p.contains(Point::new(2, 2)), Containment::Boundary);
+        assert_eq!(p.contains(Point::new(1, 1)), Containment::Inside);
+        assert_eq!(p.contains(Point::new(3, 3)), Containment::Outside);
+    }
+
+    #[test]
+    fn degenerate() {
+        // a line there and back, like the dig plan R 1, R 1, L 2
+        let p = Polygon::new(vec![Point::new(0, 0), Point::new(1, 0), Point::new(2, 0)]);
+
+        assert_eq!(p.twice_area(), 0);
+        assert_eq!(p.boundary_points(), 3);
+        assert_eq!(p.interior_points(), 0);
+        assert_eq!(p.lattice_points(), 3);
+        assert_eq!(p.contains(Point::new(1, 0)), Containment::Boundary);
+        assert_eq!(p.contains(Point::new(1, 1)), Containment::Outside);
+
+        assert_eq!(p.overlapping_edge(), Some(2));
+
+        // a 2x2 square with a spike of 2 out of its top right corner and back
+        let p = Polygon::new(
+            [(0, 0), (2, 0), (4, 0), (2, 0), (2, 2), (0, 2)]
+                .iter()
+                .map(|(x, y)| Point::new(*x, *y))
+                .collect(),
+        );
+        assert_eq!(p.boundary_points(), 10);
+        assert_eq!(p.overlapping_edge(), Some(2));
+
+        // a slanted line there and back
+        let p = Polygon::new(vec![Point::new(0, 0), Point::new(2, 4), Point::new(1, 2)]);
+        assert_eq!(p.boundary_points(), 3);
+        assert_eq!(p.overlapping_edge(), Some(1));
+
+        // touching at corners is fine as long as no point is gone through twice
+        let p = Polygon::new(vec![Point::new(0, 0), Point::new(1, 0), Point::new(1, 1)]);
+        assert_eq!(p.overlapping_edge(), None);
+    }
+}
@@ -0,0 +1,254 @@
+//! Graph searches over any state type. A puzzle only has to describe its states and
+//! the successors of a state; the graph is never built up front.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// A path found by a search: the states from a start to a goal, both included, and
+/// the total cost of the steps between them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S, C> {
+    pub cost: C,
+    pub states: Vec<S>,
+}
+
+/// Every state seen by a search, with the index of the state it was reached from
+struct Visited<S, C> {
+    states: Vec<(S, Option<usize>, C)>,
+    index: HashMap<S, usize>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> Visited<S, C> {
+    fn new() -> Self {
+        Visited {
+            states: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    /// Adds @state if it is new, returning its index
+    fn insert(&mut self, state: S, parent: Option<usize>, cost: C) -> usize {
+        let idx = self.states.len();
+
+        self.index.insert(state.clone(), idx);
+        self.states.push((state, parent, cost));
+        idx
+    }
+
+    /// Follows the parents back from @idx to a start
+    fn path(&self, idx: usize) -> Path<S, C> {
+        let cost = self.states[idx].2;
+        let mut states = Vec::new();
+        let mut current = Some(idx);
+
+        while let Some(i) = current {
+            states.push(self.states[i].0.clone());
+            current = self.states[i].1;
+        }
+
+        states.reverse();
+        Path { cost, states }
+    }
+}
+
+/// Finds the cheapest path from any of @starts to a state satisfying @is_goal.
+/// @successors gives the states one step from a state along with the cost of that
+/// step.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, successors, |_| C::default(), is_goal)
+}
+
+/// Like `dijkstra`, but states are explored in order of their cost so far plus
+/// @heuristic, an estimate of the cost left to a goal. The path is the cheapest as long
+/// as @heuristic never overestimates.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut visited = Visited::new();
+    let mut queue = BinaryHeap::new();
+
+    for s in starts {
+        if !visited.index.contains_key(&s) {
+            let estimate = heuristic(&s);
+            let idx = visited.insert(s, None, C::default());
+
+            queue.push(Reverse((estimate, C::default(), idx)));
+        }
+    }
+
+    while let Some(Reverse((_, cost, idx))) = queue.pop() {
+        // a cheaper way here was found after this one was queued
+        if cost > visited.states[idx].2 {
+            continue;
+        }
+
+        if is_goal(&visited.states[idx].0) {
+            return Some(visited.path(idx));
+        }
+
+        for (next, step) in successors(&visited.states[idx].0) {
+            let next_cost = cost + step;
+
+            let next_idx = match visited.index.get(&next) {
+                Some(&i) if visited.states[i].2 <= next_cost => continue,
+                Some(&i) => {
+                    visited.states[i].1 = Some(idx);
+                    visited.states[i].2 = next_cost;
+                    i
+                }
+                None => visited.insert(next.clone(), Some(idx), next_cost),
+            };
+
+            queue.push(Reverse((next_cost + heuristic(&next), next_cost, next_idx)));
+        }
+    }
+
+    None
+}
+
+/// Finds the path with the fewest steps from any of @starts to a state satisfying
+/// @is_goal, where @successors gives the states one step from a state
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut visited = Visited::new();
+    let mut queue = VecDeque::new();
+
+    for s in starts {
+        if !visited.index.contains_key(&s) {
+            queue.push_back(visited.insert(s, None, 0));
+        }
+    }
+
+    while let Some(idx) = queue.pop_front() {
+        if is_goal(&visited.states[idx].0) {
+            return Some(visited.path(idx));
+        }
+
+        let steps = visited.states[idx].2 + 1;
+        for next in successors(&visited.states[idx].0) {
+            if !visited.index.contains_key(&next) {
+                queue.push_back(visited.insert(next, Some(idx), steps));
+            }
+        }
+    }
+
+    None
+}
+
+/// Every state reachable from @starts in at most @limit steps (or any number of
+/// steps if there is no limit), with the fewest steps it takes to reach it. States
+/// are passed to @successors in the order they are reached.
+pub fn bfs_reach<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    limit: Option<usize>,
+) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut steps = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    for s in starts {
+        if !steps.contains_key(&s) {
+            steps.insert(s.clone(), 0);
+            queue.push_back((s, 0));
+        }
+    }
+
+    while let Some((state, n)) = queue.pop_front() {
+        if limit.is_some_and(|l| n >= l) {
+            continue;
+        }
+
+        for next in successors(&state) {
+            if !steps.contains_key(&next) {
+                steps.insert(next.clone(), n + 1);
+                queue.push_back((next, n + 1));
+            }
+        }
+    }
+
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small weighted graph where the direct edge 0 -> 3 is more expensive than
+    /// going around through 1 and 2
+    fn edges(n: &u32) -> Vec<(u32, u64)> {
+        match n {
+            0 => vec![(1, 1), (3, 10)],
+            1 => vec![(2, 2)],
+            2 => vec![(3, 3)],
+            _ => vec![],
+        }
+    }
+
+    fn edges_to(n: &u32) -> Vec<u32> {
+        edges(n).into_iter().map(|(m, _)| m).collect()
+    }
+
+    #[test]
+    fn cheapest_path() {
+        let path = dijkstra([0], edges, |n| *n == 3).unwrap();
+
+        assert_eq!(path.cost, 6);
+        assert_eq!(path.states, [0, 1, 2, 3]);
+        assert_eq!(dijkstra([1], edges, |n| *n == 0), None);
+    }
+
+    #[test]
+    fn astar_on_a_line() {
+        let successors = |n: &i64| [(n - 1, 1), (n + 1, 1)];
+        let path = astar([0], successors, |n| (10 - n).unsigned_abs(), |n| *n == 10).unwrap();
+
+        assert_eq!(path.cost, 10);
+        assert_eq!(path.states, (0..=10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn fewest_steps() {
+        let path = bfs([0], edges_to, |n| *n == 3).unwrap();
+
+        assert_eq!(path.cost, 1);
+        assert_eq!(path.states, [0, 3]);
+    }
+
+    #[test]
+    fn reach() {
+        let successors = |n: &i64| [n - 1, n + 1];
+
+        assert_eq!(bfs_reach([0], successors, Some(2)).len(), 5);
+        assert_eq!(bfs_reach([0], edges_to, None)[&3], 1);
+    }
+}